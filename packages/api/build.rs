@@ -1,7 +1,66 @@
+use std::{env, fs, path::Path};
+
 fn main() {
-    println!(
-        "cargo:rustc-env=WASMER_VERSION={}",
-        env!("CARGO_PKG_VERSION")
-    );
+    let version = wasmer_version();
+
+    println!("cargo:rustc-env=WASMER_VERSION={}", version);
+
+    // Split the version into its numeric components and its
+    // (optional) pre-release suffix, e.g. `2.1.1-rc1`.
+    let (numbers, pre) = match version.split_once('-') {
+        Some((numbers, pre)) => (numbers, pre),
+        None => (version.as_str(), ""),
+    };
+    let mut numbers = numbers.split('.');
+
+    for component in &["MAJOR", "MINOR", "PATCH"] {
+        let number = numbers.next().unwrap_or("0");
+
+        assert!(
+            number.parse::<u32>().is_ok(),
+            "The version component `{}` is not a valid number",
+            number
+        );
+
+        println!("cargo:rustc-env=WASMER_VERSION_{}={}", component, number);
+    }
+
+    println!("cargo:rustc-env=WASMER_VERSION_PRE={}", pre);
+
     pyo3_build_config::add_extension_module_link_args();
 }
+
+/// Reads the version of the `wasmer` dependency, i.e. of the Wasmer
+/// runtime, from the lockfile of the workspace. This package is named
+/// `wasmer` too, but it isn't from a registry.
+fn wasmer_version() -> String {
+    let lockfile = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("../../Cargo.lock");
+
+    println!("cargo:rerun-if-changed={}", lockfile.display());
+
+    let lockfile = fs::read_to_string(&lockfile)
+        .unwrap_or_else(|error| panic!("Failed to read `{}`: {}", lockfile.display(), error));
+
+    lockfile
+        .split("[[package]]")
+        .find_map(|package| {
+            let field = |name: &str| {
+                package.lines().find_map(|line| {
+                    line.strip_prefix(name)?
+                        .trim_start()
+                        .strip_prefix('=')
+                        .map(|value| value.trim().trim_matches('"'))
+                })
+            };
+
+            match (field("name"), field("version"), field("source")) {
+                (Some("wasmer"), Some(version), Some(source))
+                    if source.starts_with("registry+") =>
+                {
+                    Some(version.to_string())
+                }
+                _ => None,
+            }
+        })
+        .expect("The lockfile has no `wasmer` dependency")
+}
@@ -1,3 +1,44 @@
+use crate::{
+    errors::to_py_err,
+    module::{self, Module},
+    store::Store,
+    wasmer_inner::wasmer,
+};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyValueError},
+    prelude::*,
+    types::PyBytes,
+};
+use std::{borrow::Cow, fs, path::PathBuf};
+
 pub use crate::wasmer_inner::wasmer_engines::{Dylib, Universal};
 // Deprecated engines.
 pub use crate::wasmer_inner::wasmer_engines::{Native, JIT};
+
+pub fn load(store_cell: &PyCell<Store>, path_or_bytes: &PyAny) -> PyResult<Module> {
+    let store = store_cell.borrow();
+
+    if store.compiler_name().is_some() {
+        return Err(to_py_err::<PyValueError, _>(
+            "`engine.load` expects a store with a headless engine, i.e. an engine without a compiler",
+        ));
+    }
+
+    let bytes = if let Ok(bytes) = path_or_bytes.downcast::<PyBytes>() {
+        Cow::Borrowed(bytes.as_bytes())
+    } else {
+        let path = path_or_bytes.extract::<PathBuf>()?;
+
+        Cow::Owned(fs::read(path)?)
+    };
+    let artifact = module::untag_serialized(&bytes)?;
+
+    let module = unsafe { wasmer::Module::deserialize(store.inner(), artifact) }
+        .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+    Ok(Module::raw_new(
+        store_cell,
+        module,
+        module::Source::Artifact(artifact),
+    ))
+}
@@ -1,7 +1,83 @@
-use pyo3::{exceptions::PyRuntimeError, prelude::*, type_object::PyTypeObject};
-use std::string::ToString;
+use pyo3::{
+    import_exception,
+    prelude::*,
+    type_object::PyTypeObject,
+    types::{IntoPyDict, PyTuple},
+};
+use std::{
+    string::ToString,
+    sync::{Arc, Mutex},
+};
 use wasmer::RuntimeError;
 
+// The exceptions are created by `register_exceptions` when the
+// `wasmer` module is initialized, and are looked up from it.
+import_exception!(wasmer, WasmerError);
+import_exception!(wasmer, CompileError);
+import_exception!(wasmer, LinkError);
+import_exception!(wasmer, InstantiationError);
+import_exception!(wasmer, TrapError);
+
+/// Create the exception hierarchy, rooted at `WasmerError`, and add
+/// it to `module`.
+///
+/// The subclasses of `WasmerError` also inherit from the builtin
+/// `RuntimeError`, which was raised for all these errors before, so
+/// that the existing `except RuntimeError` handlers keep working.
+pub fn register_exceptions(py: Python, module: &PyModule) -> PyResult<()> {
+    let builtins = py.import("builtins")?;
+    let new_exception = |name: &str, bases: &PyTuple, doc: &str| -> PyResult<()> {
+        let exception = builtins.getattr("type")?.call1((
+            name,
+            bases,
+            [("__module__", "wasmer"), ("__doc__", doc)].into_py_dict(py),
+        ))?;
+
+        module.add(name, exception)
+    };
+
+    new_exception(
+        "WasmerError",
+        PyTuple::new(py, [builtins.getattr("Exception")?]),
+        "Base class of all the errors raised by Wasmer.",
+    )?;
+
+    let bases = PyTuple::new(
+        py,
+        [
+            module.getattr("WasmerError")?,
+            builtins.getattr("RuntimeError")?,
+        ],
+    );
+
+    new_exception(
+        "CompileError",
+        bases,
+        "Raised when a module fails to be validated or compiled.",
+    )?;
+    new_exception(
+        "LinkError",
+        bases,
+        "Raised when the imports of a module can't be resolved, e.g. a missing or incompatible import.",
+    )?;
+    new_exception(
+        "InstantiationError",
+        bases,
+        "Raised when a module fails to be instantiated, for another reason than a link error or a trap.",
+    )?;
+    new_exception(
+        "TrapError",
+        bases,
+        "Raised when a WebAssembly trap occurs, e.g. an unreachable instruction or an out of bounds memory access.",
+    )?;
+
+    Ok(())
+}
+
+/// An optional Python callable shared by an `Instance` and the
+/// functions it exports, see `Instance.set_trap_handler`.
+pub type TrapHandler = Arc<Mutex<Option<PyObject>>>;
+
 pub fn to_py_err<PyError, Error>(error: Error) -> PyErr
 where
     PyError: PyTypeObject,
@@ -13,6 +89,29 @@ where
 pub fn runtime_error_to_py_err(error: RuntimeError) -> PyErr {
     match error.downcast::<PyErr>() {
         Ok(err) => err,
-        Err(err) => to_py_err::<PyRuntimeError, _>(err),
+        Err(err) => to_py_err::<TrapError, _>(err),
+    }
+}
+
+/// Like `runtime_error_to_py_err`, but a Python exception raised by a
+/// host function is first given to the trap handler, if any. If the
+/// handler raises, its exception replaces the original one.
+pub fn trap_to_py_err(py: Python, error: RuntimeError, trap_handler: &TrapHandler) -> PyErr {
+    let error = match error.downcast::<PyErr>() {
+        Ok(error) => error,
+        Err(error) => return to_py_err::<TrapError, _>(error),
+    };
+
+    let trap_handler = match trap_handler.lock() {
+        Ok(trap_handler) => trap_handler.as_ref().map(|handler| handler.clone_ref(py)),
+        Err(_) => None,
+    };
+
+    match trap_handler {
+        Some(trap_handler) => match trap_handler.call1(py, (error.instance(py),)) {
+            Ok(_) => error,
+            Err(handler_error) => handler_error,
+        },
+        None => error,
     }
 }
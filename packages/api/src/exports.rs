@@ -1,12 +1,18 @@
 use crate::{
-    errors::to_py_err,
+    errors::{to_py_err, TrapHandler},
     externals::{Function, Global, Memory, Table},
+    interruption::InterruptionHandle,
+    store::Store,
     wasmer_inner::wasmer,
 };
 use pyo3::{
-    class::{basic::PyObjectProtocol, iter::PyIterProtocol, sequence::PySequenceProtocol},
-    exceptions::PyLookupError,
+    class::{
+        basic::PyObjectProtocol, iter::PyIterProtocol, mapping::PyMappingProtocol,
+        sequence::PySequenceProtocol,
+    },
+    exceptions::{PyKeyError, PyLookupError},
     prelude::*,
+    types::PyDict,
 };
 
 /// Represents all the exports of an instance. It is built by
@@ -42,15 +48,159 @@ use pyo3::{
 /// assert isinstance(exports.tab, Table)
 /// assert isinstance(exports.mem, Memory)
 /// ```
+///
+/// Exports can also be read by subscript, with their exact names. It
+/// is required when a name isn't a valid Python identifier:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, Function
+///
+/// module = Module(Store(), '(module (func (export "a.b")))')
+/// exports = Instance(module).exports
+///
+/// assert isinstance(exports["a.b"], Function)
+/// ```
 #[pyclass(unsendable)]
 #[derive(Clone)]
 pub struct Exports {
     inner: wasmer::Exports,
+    trap_handler: TrapHandler,
+
+    // The Python store of the module of the instance, attached to
+    // the exported externs, see `Memory.store`.
+    store: Option<Py<Store>>,
+
+    // The handle interrupting the instance, attached to the exported
+    // functions, see `InterruptionHandle`.
+    interruption_handle: Option<InterruptionHandle>,
 }
 
 impl Exports {
-    pub fn new(inner: wasmer::Exports) -> Self {
-        Self { inner }
+    pub(crate) fn new(
+        inner: wasmer::Exports,
+        trap_handler: TrapHandler,
+        store: Option<Py<Store>>,
+        interruption_handle: Option<InterruptionHandle>,
+    ) -> Self {
+        Self {
+            inner,
+            trap_handler,
+            store,
+            interruption_handle,
+        }
+    }
+
+    fn filter_by_kind<'py>(
+        &self,
+        py: Python<'py>,
+        predicate: fn(&wasmer::Extern) -> bool,
+    ) -> PyResult<&'py PyDict> {
+        let dict = PyDict::new(py);
+
+        for (name, export) in self.inner.iter().filter(|(_, export)| predicate(export)) {
+            dict.set_item(
+                name,
+                extern_to_py_object(
+                    py,
+                    export,
+                    &self.trap_handler,
+                    self.store.as_ref(),
+                    self.interruption_handle.as_ref(),
+                )?,
+            )?;
+        }
+
+        Ok(dict)
+    }
+}
+
+/// Wraps an `Extern` into its corresponding Python object, i.e. a
+/// `Function`, a `Global`, a `Memory` or a `Table`. Functions share
+/// the trap handler of the instance, and all the externs get
+/// `store`, if known, as their Python store. Functions get the
+/// interruption handle of the instance too, if any.
+pub(crate) fn extern_to_py_object(
+    py: Python,
+    export: &wasmer::Extern,
+    trap_handler: &TrapHandler,
+    store: Option<&Py<Store>>,
+    interruption_handle: Option<&InterruptionHandle>,
+) -> PyResult<PyObject> {
+    let store = store.map(|store| store.clone_ref(py));
+
+    Ok(match export {
+        wasmer::Extern::Function(function) => Py::new(
+            py,
+            Function::with_trap_handler(function.clone(), trap_handler.clone())
+                .with_store(store)
+                .with_interruption_handle(interruption_handle.cloned()),
+        )?
+        .to_object(py),
+        wasmer::Extern::Global(global) => {
+            Py::new(py, Global::raw_new(global.clone()).with_store(store))?.to_object(py)
+        }
+        wasmer::Extern::Memory(memory) => {
+            Py::new(py, Memory::raw_new(memory.clone()).with_store(store))?.to_object(py)
+        }
+        wasmer::Extern::Table(table) => {
+            Py::new(py, Table::raw_new(table.clone()).with_store(store))?.to_object(py)
+        }
+    })
+}
+
+#[pymethods]
+impl Exports {
+    /// Returns a dictionary of all the exported functions, indexed by
+    /// their names, in the export order.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Function, Memory
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "func") (param i32 i64))
+    ///       (global (export "glob") i32 (i32.const 7))
+    ///       (table (export "tab") 0 funcref)
+    ///       (memory (export "mem") 1))
+    ///     """
+    /// )
+    /// exports = Instance(module).exports
+    ///
+    /// assert list(exports.functions().keys()) == ["func"]
+    /// assert isinstance(exports.functions()["func"], Function)
+    /// assert isinstance(exports.memories()["mem"], Memory)
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn functions<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        self.filter_by_kind(py, |export| matches!(export, wasmer::Extern::Function(_)))
+    }
+
+    /// Returns a dictionary of all the exported memories, indexed by
+    /// their names, in the export order. See `Exports.functions` to
+    /// see an example.
+    #[pyo3(text_signature = "($self)")]
+    fn memories<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        self.filter_by_kind(py, |export| matches!(export, wasmer::Extern::Memory(_)))
+    }
+
+    /// Returns a dictionary of all the exported globals, indexed by
+    /// their names, in the export order. See `Exports.functions` to
+    /// see an example.
+    #[pyo3(text_signature = "($self)")]
+    fn globals<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        self.filter_by_kind(py, |export| matches!(export, wasmer::Extern::Global(_)))
+    }
+
+    /// Returns a dictionary of all the exported tables, indexed by
+    /// their names, in the export order. See `Exports.functions` to
+    /// see an example.
+    #[pyo3(text_signature = "($self)")]
+    fn tables<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        self.filter_by_kind(py, |export| matches!(export, wasmer::Extern::Table(_)))
     }
 }
 
@@ -60,26 +210,45 @@ impl PyObjectProtocol for Exports {
         let gil_guard = Python::acquire_gil();
         let py = gil_guard.python();
 
-        Ok(match self.inner.get_extern(key) {
-            Some(wasmer::Extern::Function(function)) => {
-                Py::new(py, Function::raw_new(function.clone()))?.to_object(py)
-            }
-            Some(wasmer::Extern::Global(global)) => {
-                Py::new(py, Global::raw_new(global.clone()))?.to_object(py)
-            }
-            Some(wasmer::Extern::Memory(memory)) => {
-                Py::new(py, Memory::raw_new(memory.clone()))?.to_object(py)
-            }
-            Some(wasmer::Extern::Table(table)) => {
-                Py::new(py, Table::raw_new(table.clone()))?.to_object(py)
-            }
-            _ => {
-                return Err(to_py_err::<PyLookupError, _>(format!(
-                    "Export `{}` does not exist.",
-                    key
-                )))
-            }
-        })
+        match self.inner.get_extern(key) {
+            Some(export) => extern_to_py_object(
+                py,
+                export,
+                &self.trap_handler,
+                self.store.as_ref(),
+                self.interruption_handle.as_ref(),
+            ),
+            None => Err(to_py_err::<PyLookupError, _>(format!(
+                "Export `{}` does not exist.",
+                key
+            ))),
+        }
+    }
+}
+
+#[pyproto]
+impl PyMappingProtocol for Exports {
+    /// Returns the export named `key`. Contrary to attribute access,
+    /// the name is used as is, so that any export name is reachable,
+    /// including names that aren't Python identifiers, like `a.b` or
+    /// `x-y`. A `KeyError` is raised if the export doesn't exist.
+    fn __getitem__(&self, key: &str) -> PyResult<PyObject> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        match self.inner.get_extern(key) {
+            Some(export) => extern_to_py_object(
+                py,
+                export,
+                &self.trap_handler,
+                self.store.as_ref(),
+                self.interruption_handle.as_ref(),
+            ),
+            None => Err(to_py_err::<PyKeyError, _>(format!(
+                "Export `{}` does not exist.",
+                key
+            ))),
+        }
     }
 }
 
@@ -100,6 +269,9 @@ impl PyIterProtocol for Exports {
                 .map(|(name, export)| (name.clone(), export.clone()))
                 .collect(),
             index: 0,
+            trap_handler: slf.trap_handler.clone(),
+            store: slf.store.clone(),
+            interruption_handle: slf.interruption_handle.clone(),
         }
     }
 }
@@ -129,6 +301,9 @@ impl PyIterProtocol for Exports {
 pub struct ExportsIterator {
     vector: Vec<(String, wasmer::Extern)>,
     index: usize,
+    trap_handler: TrapHandler,
+    store: Option<Py<Store>>,
+    interruption_handle: Option<InterruptionHandle>,
 }
 
 #[pyproto]
@@ -145,20 +320,13 @@ impl PyIterProtocol for ExportsIterator {
             Some((name, export)) => (
                 Ok(Some((
                     name.clone(),
-                    match export {
-                        wasmer::Extern::Function(function) => {
-                            Py::new(py, Function::raw_new(function.clone()))?.to_object(py)
-                        }
-                        wasmer::Extern::Global(global) => {
-                            Py::new(py, Global::raw_new(global.clone()))?.to_object(py)
-                        }
-                        wasmer::Extern::Memory(memory) => {
-                            Py::new(py, Memory::raw_new(memory.clone()))?.to_object(py)
-                        }
-                        wasmer::Extern::Table(table) => {
-                            Py::new(py, Table::raw_new(table.clone()))?.to_object(py)
-                        }
-                    },
+                    extern_to_py_object(
+                        py,
+                        export,
+                        &slf.trap_handler,
+                        slf.store.as_ref(),
+                        slf.interruption_handle.as_ref(),
+                    )?,
                 ))),
                 slf.index + 1,
             ),
@@ -1,16 +1,19 @@
 use crate::{
-    errors::{runtime_error_to_py_err, to_py_err},
+    errors::{to_py_err, trap_to_py_err, TrapHandler},
+    interruption::InterruptionHandle,
     store::Store,
     types::FunctionType,
-    values::{to_py_object, to_wasm_value},
-    wasmer_inner::wasmer,
+    values::{to_py_object, to_wasm_value, Value},
+    wasmer_inner::{wasmer, wasmer_vm},
 };
 use pyo3::{
-    exceptions::{PyRuntimeError, PyValueError},
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
     prelude::*,
     types::{PyDict, PyTuple},
+    PyNativeType,
 };
-use std::sync::Arc;
+use std::{cell::RefCell, sync::Arc};
+use wasmer::Exportable;
 
 /// Represents a WebAssembly function instance.
 ///
@@ -77,6 +80,13 @@ use std::sync::Arc;
 /// assert function_type.results == [Type.I64, Type.I32]
 /// ```
 ///
+/// A host function can also return `Value` objects. Their exact bits
+/// are then preserved, which isn't guaranteed with Python `float`s:
+/// a `float` is always 64 bits wide, so a `f32` NaN payload may be
+/// altered when converted from or to a `float`. Use
+/// `Value.f32_from_bits` or `Value.f64_from_bits` to return a NaN
+/// with a specific payload.
+///
 /// ## With `FunctionType`
 ///
 /// Second, the same code but without annotations and a `FunctionType`:
@@ -94,16 +104,204 @@ use std::sync::Arc;
 #[pyo3(text_signature = "(store, function, function_type)")]
 pub struct Function {
     inner: wasmer::Function,
+    trap_handler: TrapHandler,
+
+    // Whether the function has been created from a Python callable,
+    // see `Function.is_host_function`.
+    is_host_function: bool,
+
+    // The Python store the function belongs to, if known, see
+    // `Function.store`.
+    store: Option<Py<Store>>,
+
+    // The handle interrupting the instance of the function, if it
+    // has been compiled by an interruptible engine, see `Store`.
+    interruption_handle: Option<InterruptionHandle>,
+
+    // The function calling `inner` from WebAssembly, when `inner`
+    // can't be called from the host, see `Function::forwarder`.
+    forwarder: RefCell<Option<wasmer::Function>>,
 }
 
 impl Function {
     pub fn raw_new(inner: wasmer::Function) -> Self {
-        Self { inner }
+        Self::with_trap_handler(inner, TrapHandler::default())
+    }
+
+    pub(crate) fn with_trap_handler(inner: wasmer::Function, trap_handler: TrapHandler) -> Self {
+        Self {
+            inner,
+            trap_handler,
+            is_host_function: false,
+            store: None,
+            interruption_handle: None,
+            forwarder: RefCell::new(None),
+        }
+    }
+
+    /// Attaches the Python `Store` the extern belongs to, see the
+    /// `store` getter.
+    pub(crate) fn with_store(self, store: Option<Py<Store>>) -> Self {
+        Self { store, ..self }
+    }
+
+    /// Attaches the handle interrupting the instance of the function,
+    /// see `InterruptionHandle`.
+    pub(crate) fn with_interruption_handle(
+        self,
+        interruption_handle: Option<InterruptionHandle>,
+    ) -> Self {
+        Self {
+            interruption_handle,
+            ..self
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Function {
         &self.inner
     }
+
+    /// The function as a `funcref` value. A function created from a
+    /// Python callable has no code WebAssembly can call until an
+    /// instance imports it, so it cannot be referenced, e.g. stored
+    /// in a table: `TypeError` is raised instead.
+    pub(crate) fn to_funcref(&self) -> PyResult<wasmer::Value> {
+        let is_callable_from_wasm = match self.inner.to_export() {
+            wasmer::Export::Function(function) => !function.vm_function.address.is_null(),
+            _ => unreachable!("A function exports a function"),
+        };
+
+        if !is_callable_from_wasm {
+            return Err(to_py_err::<PyTypeError, _>(
+                "A host function cannot be used as a `funcref`, use the function exported by an instance importing it instead",
+            ));
+        }
+
+        Ok(wasmer::Value::FuncRef(Some(self.inner.clone())))
+    }
+
+    /// Wasmer can't call a native function without a trampoline from
+    /// the host, e.g. a function read from a `funcref` value. Such a
+    /// function is imported by an instance exporting a function
+    /// calling it, which is returned instead. It is `None` when
+    /// `inner` can be called directly.
+    fn forwarder(&self) -> Option<wasmer::Function> {
+        let vm_function = match self.inner.to_export() {
+            wasmer::Export::Function(function) => function.vm_function,
+            _ => unreachable!("A function exports a function"),
+        };
+
+        if vm_function.call_trampoline.is_some()
+            || matches!(vm_function.kind, wasmer_vm::VMFunctionKind::Dynamic)
+        {
+            return None;
+        }
+
+        let mut forwarder = self.forwarder.borrow_mut();
+
+        if forwarder.is_none() {
+            let function_type = self.inner.ty();
+            let params = wat_types("param", function_type.params());
+            let results = wat_types("result", function_type.results());
+            let locals = (0..function_type.params().len())
+                .map(|index| format!(" (local.get {})", index))
+                .collect::<String>();
+            let wat = format!(
+                r#"(module
+  (import "env" "function" (func $function{params}{results}))
+  (func (export "function"){params}{results}
+    (call $function{locals})))"#,
+                params = params,
+                results = results,
+                locals = locals,
+            );
+
+            let store = self.inner.store();
+            let module = wasmer::Module::new(store, wat).expect("The forwarder is a valid module");
+            let instance = wasmer::Instance::new(
+                &module,
+                &wasmer::imports! {
+                    "env" => {
+                        "function" => self.inner.clone(),
+                    },
+                },
+            )
+            .expect("The forwarder imports the function");
+
+            *forwarder = Some(
+                instance
+                    .exports
+                    .get_function("function")
+                    .expect("The forwarder exports the function")
+                    .clone(),
+            );
+        }
+
+        forwarder.clone()
+    }
+
+    fn raw_call(&self, arguments: &PyTuple) -> PyResult<Vec<wasmer::Value>> {
+        let py = arguments.py();
+        let function_type = self.inner.ty();
+        let params = function_type.params();
+
+        if arguments.len() != params.len() {
+            return Err(to_py_err::<PyTypeError, _>(format!(
+                "The function expects {} argument{}, got {}",
+                params.len(),
+                if params.len() == 1 { "" } else { "s" },
+                arguments.len()
+            )));
+        }
+
+        let arguments: Vec<wasmer::Value> = arguments
+            .iter()
+            .zip(params)
+            .enumerate()
+            .map(|(position, (value, ty))| {
+                // Keep the kind of the error, but name the argument.
+                to_wasm_value((value, *ty)).map_err(|error| {
+                    PyErr::from_type(
+                        error.ptype(py),
+                        format!(
+                            "Argument {} cannot be converted to `{:?}`: {}",
+                            position + 1,
+                            ty,
+                            error.pvalue(py)
+                        ),
+                    )
+                })
+            })
+            .collect::<PyResult<_>>()?;
+
+        self.call_values(py, &arguments)
+    }
+
+    /// Calls the function with arguments that are already WebAssembly
+    /// values. It is the path every call from the host takes, so that
+    /// it can be interrupted, and pending signals are handled.
+    pub(crate) fn call_values(
+        &self,
+        py: Python,
+        arguments: &[wasmer::Value],
+    ) -> PyResult<Vec<wasmer::Value>> {
+        let forwarder = self.forwarder();
+        let function = forwarder.as_ref().unwrap_or(&self.inner);
+        let results = match &self.interruption_handle {
+            Some(interruption_handle) => interruption_handle.call(|| function.call(arguments)),
+            None => function.call(arguments),
+        };
+
+        // A signal (e.g. `SIGINT` from Ctrl-C) may have been received
+        // while the GIL was held by the call. Let its handler run now,
+        // so that `KeyboardInterrupt` isn't delayed further. It comes
+        // first: an interrupted call has trapped.
+        py.check_signals()?;
+
+        results
+            .map(<[_]>::into_vec)
+            .map_err(|error| trap_to_py_err(py, error, &self.trap_handler))
+    }
 }
 
 #[pymethods]
@@ -111,7 +309,7 @@ impl Function {
     #[new]
     fn new(
         py: Python,
-        store: &Store,
+        store: &PyCell<Store>,
         py_function: &PyAny,
         function_type: Option<&FunctionType>,
     ) -> PyResult<Self> {
@@ -184,7 +382,7 @@ impl Function {
         };
 
         let host_function = wasmer::Function::new_with_env(
-            store.inner(),
+            store.borrow().inner(),
             &wasmer::FunctionType::new(argument_types, result_types),
             environment,
             |environment,
@@ -193,6 +391,14 @@ impl Function {
                 let gil = Python::acquire_gil();
                 let py = gil.python();
 
+                // Each call from the guest to the host is an
+                // opportunity to handle the pending signals, e.g. to
+                // interrupt a long running guest with Ctrl-C: the
+                // raised `KeyboardInterrupt` unwinds the guest and is
+                // re-raised as is by the caller.
+                py.check_signals()
+                    .map_err(|error| wasmer::RuntimeError::user(Box::new(error)))?;
+
                 let to_py_object = to_py_object(py);
                 let arguments: Vec<PyObject> = arguments.iter().map(to_py_object).collect();
 
@@ -226,24 +432,28 @@ impl Function {
             },
         );
 
-        Ok(Self::raw_new(host_function))
+        Ok(Self {
+            is_host_function: true,
+            ..Self::raw_new(host_function).with_store(Some(store.into()))
+        })
     }
 
     /// Calls the function as a regular Python function.
+    ///
+    /// Signals, like `SIGINT` sent by Ctrl-C, are handled when the
+    /// call returns, and each time the WebAssembly code calls a host
+    /// function. A `KeyboardInterrupt` raised by a host function
+    /// unwinds the WebAssembly code, and is re-raised as is.
+    ///
+    /// A WebAssembly loop that never calls the host can only be
+    /// interrupted by Ctrl-C if the module has been compiled by an
+    /// interruptible store, see the `interruptible` argument of
+    /// `Store`: the call then raises `KeyboardInterrupt`. Otherwise,
+    /// the signal is handled when the call returns.
     #[call]
     #[args(arguments = "*")]
     fn __call__<'p>(&self, py: Python<'p>, arguments: &PyTuple) -> PyResult<PyObject> {
-        let arguments: Vec<wasmer::Value> = arguments
-            .iter()
-            .zip(self.inner.ty().params())
-            .map(|(value, ty)| to_wasm_value((value, *ty)))
-            .collect::<PyResult<_>>()?;
-
-        let results = self
-            .inner
-            .call(&arguments)
-            .map(<[_]>::into_vec)
-            .map_err(runtime_error_to_py_err)?;
+        let results = self.raw_call(arguments)?;
 
         let to_py_object = to_py_object(py);
 
@@ -258,6 +468,39 @@ impl Function {
         })
     }
 
+    /// Calls the function like `__call__`, but returns the results as
+    /// a list of `Value` objects, preserving their exact WebAssembly
+    /// types instead of coercing them to Python `int` or `float`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Value
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "swap") (param i32 i64) (result i64 i32)
+    ///         local.get 1
+    ///         local.get 0))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// results = instance.exports.swap.call_typed(1, 2)
+    ///
+    /// assert [repr(result) for result in results] == ['I64(2)', 'I32(1)']
+    /// ```
+    #[pyo3(text_signature = "($self, *arguments)")]
+    #[args(arguments = "*")]
+    fn call_typed(&self, arguments: &PyTuple) -> PyResult<Vec<Value>> {
+        Ok(self
+            .raw_call(arguments)?
+            .into_iter()
+            .map(Value::raw_new)
+            .collect())
+    }
+
     /// Returns the type of the function as a `FunctionType` object.
     ///
     /// ## Example
@@ -289,6 +532,184 @@ impl Function {
     fn ty(&self) -> FunctionType {
         self.inner.ty().into()
     }
+
+    /// Checks whether the function has exactly the given
+    /// `FunctionType`, i.e. the same parameters and results, in the
+    /// same order. It helps to check a host function can satisfy an
+    /// imported function before registering it in an import object.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Function
+    ///
+    /// def sum(x: int, y: int) -> int:
+    ///     return x + y
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     '(module (import "math" "sum" (func (param i32 i32) (result i32))))'
+    /// )
+    /// function = Function(store, sum)
+    ///
+    /// assert function.type_matches(module.imports[0].type)
+    /// ```
+    #[pyo3(text_signature = "($self, function_type)")]
+    fn type_matches(&self, function_type: &FunctionType) -> bool {
+        let function_type: wasmer::FunctionType = function_type.into();
+
+        self.inner.ty() == &function_type
+    }
+
+    /// Creates a `Function` from a function exported by an instance,
+    /// so that it can be imported by another instance. Both functions
+    /// share the same underlying WebAssembly function.
+    ///
+    /// Exported functions can already be registered as imports as
+    /// is; this constructor makes the intent explicit and, if `store`
+    /// is given, checks that the function belongs to it (see
+    /// `Store.id`), since a function can only be imported by a module
+    /// compiled with the same store. A `ValueError` is raised
+    /// otherwise.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Function
+    ///
+    /// store = Store()
+    /// library = Instance(
+    ///     Module(
+    ///         store,
+    ///         """
+    ///         (module
+    ///           (func (export "double") (param i32) (result i32)
+    ///             local.get 0
+    ///             i32.const 2
+    ///             i32.mul))
+    ///         """
+    ///     )
+    /// )
+    /// program = Instance(
+    ///     Module(
+    ///         store,
+    ///         """
+    ///         (module
+    ///           (import "library" "double" (func $double (param i32) (result i32)))
+    ///           (func (export "quadruple") (param i32) (result i32)
+    ///             local.get 0
+    ///             call $double
+    ///             call $double))
+    ///         """
+    ///     ),
+    ///     {"library": {"double": Function.from_export(library.exports.double, store)}}
+    /// )
+    ///
+    /// assert program.exports.quadruple(3) == 12
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(exported_function, store=None)")]
+    fn from_export(exported_function: &Function, store: Option<&PyCell<Store>>) -> PyResult<Self> {
+        if let Some(store) = store {
+            if !wasmer::Store::same(exported_function.inner.store(), store.borrow().inner()) {
+                return Err(to_py_err::<PyValueError, _>(
+                    "The exported function belongs to a different store",
+                ));
+            }
+        }
+
+        // The function keeps the trap handler and the interruption
+        // handle of the instance exporting it.
+        Ok(Self::with_trap_handler(
+            exported_function.inner.clone(),
+            exported_function.trap_handler.clone(),
+        )
+        .with_store(
+            exported_function
+                .store
+                .clone()
+                .or_else(|| store.map(Into::into)),
+        )
+        .with_interruption_handle(exported_function.interruption_handle.clone()))
+    }
+
+    /// Whether the function is a host function, i.e. it has been
+    /// created from a Python callable with `Function(store,
+    /// callable)`, rather than obtained from the exports of an
+    /// instance.
+    ///
+    /// When a host function raises an exception, calling it raises
+    /// the original exception; when a WebAssembly function traps,
+    /// calling it raises a `TrapError` describing the trap.
+    ///
+    /// The flag depends on how this `Function` object has been
+    /// created: a host function read back from an `ImportObject`, or
+    /// re-exported by an instance, isn't reported as a host function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Function
+    ///
+    /// def answer() -> int:
+    ///     return 42
+    ///
+    /// store = Store()
+    /// instance = Instance(Module(store, '(module (func (export "f")))'))
+    ///
+    /// assert Function(store, answer).is_host_function == True
+    /// assert instance.exports.f.is_host_function == False
+    /// ```
+    #[getter]
+    fn is_host_function(&self) -> bool {
+        self.is_host_function
+    }
+
+    /// The store this function belongs to: the `Store` it has been
+    /// created with, or the store of the module of the instance
+    /// exporting it. It is `None` when the store isn't known, e.g.
+    /// for a function read from an `ImportObject`.
+    ///
+    /// ## Example
+    ///
+    /// See `Memory.store`.
+    #[getter]
+    fn store(&self, py: Python) -> Option<Py<Store>> {
+        self.store.as_ref().map(|store| store.clone_ref(py))
+    }
+
+    /// The identifier of the store this function belongs to. See
+    /// `Store.id`. It is `None` when the store isn't known, see
+    /// `Function.store`.
+    #[getter]
+    pub(crate) fn store_id(&self, py: Python) -> Option<u64> {
+        self.store.as_ref().map(|store| store.borrow(py).id())
+    }
+}
+
+/// Formats `types` as a WebAssembly text `param` or `result` clause,
+/// see `Function::forwarder`.
+fn wat_types(clause: &str, types: &[wasmer::Type]) -> String {
+    if types.is_empty() {
+        return String::new();
+    }
+
+    let types = types
+        .iter()
+        .map(|ty| match ty {
+            wasmer::Type::I32 => "i32",
+            wasmer::Type::I64 => "i64",
+            wasmer::Type::F32 => "f32",
+            wasmer::Type::F64 => "f64",
+            wasmer::Type::V128 => "v128",
+            wasmer::Type::ExternRef => "externref",
+            wasmer::Type::FuncRef => "funcref",
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    format!(" ({} {})", clause, types)
 }
 
 enum MappedType {
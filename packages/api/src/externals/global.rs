@@ -8,6 +8,7 @@ use crate::{
 use pyo3::{
     exceptions::{PyRuntimeError, PyValueError},
     prelude::*,
+    PyNativeType,
 };
 
 /// Represents a WebAssembly global instance.
@@ -38,35 +39,133 @@ use pyo3::{
 ///
 /// assert global_.mutable == True
 /// ```
+///
+/// A `v128` global requires the SIMD feature to be enabled on the
+/// store, see `Store.features`. A `RuntimeError` is raised otherwise.
+///
+/// ```py
+/// from wasmer import Store, Global, Value, Type
+///
+/// store = Store()
+/// global_ = Global(store, Value.v128(42), mutable=True)
+///
+/// assert global_.value == 42
+/// assert global_.type.type == Type.V128
+/// ```
 #[pyclass(unsendable)]
 #[pyo3(text_signature = "(store, value, mutable)")]
 pub struct Global {
     inner: wasmer::Global,
+
+    // The callbacks registered with `Global.on_change`.
+    change_callbacks: Vec<PyObject>,
+
+    // The Python store the global belongs to, if known, see
+    // `Global.store`.
+    store: Option<Py<Store>>,
 }
 
 impl Global {
     pub fn raw_new(inner: wasmer::Global) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            change_callbacks: Vec::new(),
+            store: None,
+        }
+    }
+
+    /// Attaches the Python `Store` the extern belongs to, see the
+    /// `store` getter.
+    pub(crate) fn with_store(self, store: Option<Py<Store>>) -> Self {
+        Self { store, ..self }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Global {
         &self.inner
     }
+
+    fn new_with_value(
+        store: &PyCell<Store>,
+        value: wasmer::Value,
+        mutable: bool,
+    ) -> PyResult<Self> {
+        let store_ref = store.borrow();
+
+        if let wasmer::Value::V128(_) = value {
+            if !store_ref.enabled_features().simd {
+                return Err(to_py_err::<PyRuntimeError, _>(
+                    "A `v128` global requires the SIMD feature, which is not enabled on the store",
+                ));
+            }
+        }
+
+        let global = if mutable {
+            wasmer::Global::new_mut(store_ref.inner(), value)
+        } else {
+            wasmer::Global::new(store_ref.inner(), value)
+        };
+
+        Ok(Self::raw_new(global).with_store(Some(store.into())))
+    }
 }
 
 #[pymethods]
 impl Global {
     #[new]
-    fn new(store: &Store, value: &Value, mutable: Option<bool>) -> Self {
-        let store = store.inner();
-        let value = value.inner().clone();
+    fn new(store: &PyCell<Store>, value: &Value, mutable: Option<bool>) -> PyResult<Self> {
+        Self::new_with_value(store, value.inner().clone(), mutable.unwrap_or(false))
+    }
 
-        Self {
-            inner: match mutable {
-                Some(true) => wasmer::Global::new_mut(store, value),
-                _ => wasmer::Global::new(store, value),
-            },
-        }
+    /// Build a global of kind `i32`. It is a shortcut for
+    /// `Global(store, Value.i32(value), mutable)`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Global, Type
+    ///
+    /// store = Store()
+    /// global_ = Global.i32(store, 42, mutable=True)
+    ///
+    /// assert global_.value == 42
+    /// assert global_.type.type == Type.I32
+    /// assert global_.mutable == True
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(store, value, /, mutable=False)")]
+    #[args(mutable = "false")]
+    fn i32(store: &PyCell<Store>, value: i32, mutable: bool) -> PyResult<Self> {
+        Self::new_with_value(store, wasmer::Value::I32(value), mutable)
+    }
+
+    /// Build a global of kind `i64`. It is a shortcut for
+    /// `Global(store, Value.i64(value), mutable)`. See `Global.i32`
+    /// to see an example.
+    #[staticmethod]
+    #[pyo3(text_signature = "(store, value, /, mutable=False)")]
+    #[args(mutable = "false")]
+    fn i64(store: &PyCell<Store>, value: i64, mutable: bool) -> PyResult<Self> {
+        Self::new_with_value(store, wasmer::Value::I64(value), mutable)
+    }
+
+    /// Build a global of kind `f32`. It is a shortcut for
+    /// `Global(store, Value.f32(value), mutable)`. See `Global.i32`
+    /// to see an example.
+    #[staticmethod]
+    #[pyo3(text_signature = "(store, value, /, mutable=False)")]
+    #[args(mutable = "false")]
+    fn f32(store: &PyCell<Store>, value: f32, mutable: bool) -> PyResult<Self> {
+        Self::new_with_value(store, wasmer::Value::F32(value), mutable)
+    }
+
+    /// Build a global of kind `f64`. It is a shortcut for
+    /// `Global(store, Value.f64(value), mutable)`. See `Global.i32`
+    /// to see an example.
+    #[staticmethod]
+    #[pyo3(text_signature = "(store, value, /, mutable=False)")]
+    #[args(mutable = "false")]
+    fn f64(store: &PyCell<Store>, value: f64, mutable: bool) -> PyResult<Self> {
+        Self::new_with_value(store, wasmer::Value::F64(value), mutable)
     }
 
     /// Checks whether the global is mutable.
@@ -123,6 +222,53 @@ impl Global {
             .set(to_wasm_value((value, ty.ty))?)
             .map_err(to_py_err::<PyValueError, _>)?;
 
+        if !self.change_callbacks.is_empty() {
+            let py = value.py();
+            let new_value = to_py_object(py)(&self.inner.get());
+
+            for callback in &self.change_callbacks {
+                callback.call1(py, (new_value.clone_ref(py),))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registers a callback that is called with the new value each
+    /// time the value of the global is successfully set from Python,
+    /// with `global_.value = x`.
+    ///
+    /// Only the sets done from the host through this `Global` object
+    /// are observed: the writes done by WebAssembly code (with
+    /// `global.set`) are not, and neither are the sets done through
+    /// another `Global` object wrapping the same global, e.g. as
+    /// returned by another access to `instance.exports`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Global, Value
+    ///
+    /// store = Store()
+    /// global_ = Global(store, Value.i32(42), mutable=True)
+    ///
+    /// changes = []
+    /// global_.on_change(changes.append)
+    ///
+    /// global_.value = 153
+    ///
+    /// assert changes == [153]
+    /// ```
+    #[pyo3(text_signature = "($self, callback)")]
+    fn on_change(&mut self, callback: &PyAny) -> PyResult<()> {
+        if !callback.is_callable() {
+            return Err(to_py_err::<PyValueError, _>(
+                "The callback must be a callable",
+            ));
+        }
+
+        self.change_callbacks.push(callback.into());
+
         Ok(())
     }
 
@@ -145,4 +291,25 @@ impl Global {
     fn ty(&self) -> GlobalType {
         self.inner.ty().into()
     }
+
+    /// The store this global belongs to: the `Store` it has been
+    /// created with, or the store of the module of the instance
+    /// exporting it. It is `None` when the store isn't known, e.g.
+    /// for a global read from an `ImportObject`.
+    ///
+    /// ## Example
+    ///
+    /// See `Memory.store`.
+    #[getter]
+    fn store(&self, py: Python) -> Option<Py<Store>> {
+        self.store.as_ref().map(|store| store.clone_ref(py))
+    }
+
+    /// The identifier of the store this global belongs to. See
+    /// `Store.id`. It is `None` when the store isn't known, see
+    /// `Global.store`.
+    #[getter]
+    pub(crate) fn store_id(&self, py: Python) -> Option<u64> {
+        self.store.as_ref().map(|store| store.borrow(py).id())
+    }
 }
@@ -5,10 +5,18 @@ use crate::{
         Uint16Array, Uint32Array, Uint64Array, Uint8Array,
     },
     store::Store,
-    types::MemoryType,
+    tunables::grow_from_host,
+    types::{MemoryType, Type},
     wasmer_inner::wasmer,
 };
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
+use pyo3::{
+    buffer::PyBuffer,
+    exceptions::{PyIndexError, PyRuntimeError, PyTypeError, PyValueError},
+    prelude::*,
+    types::{IntoPyDict, PyBytes},
+    PyNativeType,
+};
+use std::{cell::Cell, convert::TryFrom};
 
 /// A WebAssembly memory instance.
 ///
@@ -40,6 +48,21 @@ use pyo3::{exceptions::PyRuntimeError, prelude::*};
 /// assert memory.size == 3
 /// ```
 ///
+/// Creates a `Memory` with initial data, written at offset 0. The
+/// data can be any object implementing the buffer protocol, like
+/// `bytes`, `bytearray` or `memoryview`. The memory grows if needed
+/// to fit the data:
+///
+/// ```py
+/// from wasmer import Store, Memory, MemoryType
+///
+/// store = Store()
+/// memory_type = MemoryType(minimum=1)
+/// memory = Memory(store, memory_type, data=b'Wasmer')
+///
+/// assert bytearray(memory.buffer)[0:6] == b'Wasmer'
+/// ```
+///
 /// Gets a memory from the exports of an instance:
 ///
 /// ```py
@@ -53,29 +76,132 @@ use pyo3::{exceptions::PyRuntimeError, prelude::*};
 /// assert isinstance(memory, Memory)
 /// ```
 #[pyclass(unsendable)]
-#[pyo3(text_signature = "(store, memory_type)")]
+#[pyo3(text_signature = "(store, memory_type, data=None)")]
 pub struct Memory {
     inner: wasmer::Memory,
+
+    // The Python store the memory belongs to, if known, see
+    // `Memory.store`.
+    store: Option<Py<Store>>,
 }
 
 impl Memory {
     pub fn raw_new(inner: wasmer::Memory) -> Self {
-        Self { inner }
+        Self { inner, store: None }
+    }
+
+    /// Attaches the Python `Store` the extern belongs to, see the
+    /// `store` getter.
+    pub(crate) fn with_store(self, store: Option<Py<Store>>) -> Self {
+        Self { store, ..self }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Memory {
         &self.inner
     }
+
+    /// Reads an element type given as a string, like `'i32'`, or as
+    /// a `Type`, like `Type.I32`, see `Memory.view`.
+    fn element_type_name(element_type: &PyAny) -> PyResult<&str> {
+        if let Ok(element_type) = element_type.extract::<&str>() {
+            return Ok(element_type);
+        }
+
+        match element_type.extract::<Type>() {
+            Ok(Type::I32) => Ok("i32"),
+            Ok(Type::I64) => Ok("i64"),
+            Ok(Type::F32) => Ok("f32"),
+            Ok(Type::F64) => Ok("f64"),
+            _ => Err(to_py_err::<PyValueError, _>(format!(
+                "There is no memory view for the element type `{}`",
+                element_type.repr()?
+            ))),
+        }
+    }
 }
 
 #[pymethods]
 impl Memory {
     #[new]
-    fn new(store: &Store, memory_type: &MemoryType) -> PyResult<Self> {
-        Ok(Self::raw_new(
-            wasmer::Memory::new(store.inner(), memory_type.into())
-                .map_err(to_py_err::<PyRuntimeError, _>)?,
-        ))
+    #[args(data = "None")]
+    fn new(
+        py: Python,
+        store: &PyCell<Store>,
+        memory_type: &MemoryType,
+        data: Option<&PyAny>,
+    ) -> PyResult<Self> {
+        let memory = wasmer::Memory::new(store.borrow().inner(), memory_type.into())
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        if let Some(data) = data {
+            let data = PyBuffer::<u8>::get(data)?;
+            let required_pages = Self::pages_for_bytes(data.item_count() as u64);
+            let current_pages = u64::from(memory.size().0);
+
+            // Without a declared maximum, the memory can't grow beyond
+            // the limit of WebAssembly itself. Checking it before
+            // copying the data avoids a useless copy.
+            let maximum = memory
+                .ty()
+                .maximum
+                .map_or(wasmer::WASM_MAX_PAGES, |pages| pages.0);
+
+            if required_pages > u64::from(maximum) {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The data ({} bytes) exceeds the memory maximum size ({} pages)",
+                    data.item_count(),
+                    maximum
+                )));
+            }
+
+            let data = data.to_vec(py)?;
+
+            if required_pages > current_pages {
+                grow_from_host(|| memory.grow((required_pages - current_pages) as u32))
+                    .map_err(to_py_err::<PyRuntimeError, _>)?;
+            }
+
+            let view = memory.view::<u8>();
+
+            for (cell, byte) in view[..data.len()].iter().zip(&data) {
+                cell.set(*byte);
+            }
+        }
+
+        Ok(Self::raw_new(memory).with_store(Some(store.into())))
+    }
+
+    /// The size of a WebAssembly page, in bytes, i.e. 65536 (64Ki).
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Memory
+    ///
+    /// assert Memory.PAGE_SIZE == 65536
+    /// ```
+    #[classattr]
+    const PAGE_SIZE: u32 = wasmer::WASM_PAGE_SIZE as u32;
+
+    /// Returns the number of pages needed to hold `number_of_bytes`
+    /// bytes.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Memory
+    ///
+    /// assert Memory.pages_for_bytes(0) == 0
+    /// assert Memory.pages_for_bytes(1) == 1
+    /// assert Memory.pages_for_bytes(65536) == 1
+    /// assert Memory.pages_for_bytes(65537) == 2
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(number_of_bytes)")]
+    fn pages_for_bytes(number_of_bytes: u64) -> u64 {
+        let page_size = wasmer::WASM_PAGE_SIZE as u64;
+
+        number_of_bytes.div_ceil(page_size)
     }
 
     /// Returns the size (in pages) of the `Memory`.
@@ -114,6 +240,31 @@ impl Memory {
         self.inner.data_size()
     }
 
+    /// Returns the maximum size (in bytes) the `Memory` can grow to,
+    /// computed from the maximum number of pages of its
+    /// `MemoryType`, or `None` if the memory is unbounded.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    ///
+    /// memory = Memory(store, MemoryType(minimum=1, maximum=3))
+    /// assert memory.max_data_size == 196608
+    ///
+    /// memory = Memory(store, MemoryType(minimum=1))
+    /// assert memory.max_data_size == None
+    /// ```
+    #[getter]
+    fn max_data_size(&self) -> Option<u64> {
+        self.inner
+            .ty()
+            .maximum
+            .map(|pages| u64::from(pages.0) * wasmer::WASM_PAGE_SIZE as u64)
+    }
+
     /// Grow memory by the specified amount of WebAssembly pages.
     ///
     /// ## Example
@@ -133,12 +284,84 @@ impl Memory {
     /// ```
     #[pyo3(text_signature = "($self, number_of_pages)")]
     fn grow(&self, number_of_pages: u32) -> PyResult<u32> {
-        self.inner
-            .grow(number_of_pages)
+        grow_from_host(|| self.inner.grow(number_of_pages))
             .map(|pages| pages.0)
             .map_err(to_py_err::<PyRuntimeError, _>)
     }
 
+    /// Grow memory by at least `number_of_bytes` bytes, rounded up to
+    /// whole WebAssembly pages. It returns the previous size of the
+    /// memory in bytes, i.e. the offset of the newly available bytes.
+    ///
+    /// A `ValueError` is raised if `number_of_bytes` isn't positive,
+    /// and a `RuntimeError` if the memory can't grow that much.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1))
+    ///
+    /// assert memory.grow_bytes(10) == 65536
+    /// assert memory.data_size == 131072
+    /// ```
+    #[pyo3(text_signature = "($self, number_of_bytes)")]
+    fn grow_bytes(&self, number_of_bytes: i64) -> PyResult<u64> {
+        if number_of_bytes <= 0 {
+            return Err(to_py_err::<PyValueError, _>(format!(
+                "The number of bytes to grow by must be positive, given {}",
+                number_of_bytes
+            )));
+        }
+
+        let number_of_pages = u32::try_from(Self::pages_for_bytes(number_of_bytes as u64))
+            .map_err(|_| {
+                to_py_err::<PyRuntimeError, _>(format!(
+                    "Cannot grow the memory by {} bytes",
+                    number_of_bytes
+                ))
+            })?;
+
+        grow_from_host(|| self.inner.grow(number_of_pages))
+            .map(|pages| u64::from(pages.0) * wasmer::WASM_PAGE_SIZE as u64)
+            .map_err(to_py_err::<PyRuntimeError, _>)
+    }
+
+    /// Ensures the memory is at least `min_pages` WebAssembly pages
+    /// large, growing it if necessary, but never shrinking it. It
+    /// returns the resulting size (in pages).
+    ///
+    /// A `RuntimeError` is raised if the memory can't grow, e.g. if
+    /// `min_pages` is larger than the memory maximum.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=3))
+    ///
+    /// assert memory.ensure_size(5) == 5
+    /// assert memory.ensure_size(2) == 5
+    /// assert memory.size == 5
+    /// ```
+    #[pyo3(text_signature = "($self, min_pages)")]
+    fn ensure_size(&self, min_pages: u32) -> PyResult<u32> {
+        let current_pages = self.inner.size().0;
+
+        if current_pages >= min_pages {
+            return Ok(current_pages);
+        }
+
+        grow_from_host(|| self.inner.grow(min_pages - current_pages))
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        Ok(self.inner.size().0)
+    }
+
     /// Creates a Python buffer to read and write the memory data. See
     /// the `Buffer` class to learn more.
     ///
@@ -290,6 +513,495 @@ impl Memory {
         }
     }
 
+    /// Creates a read-and-write view over the memory data where the
+    /// kind of the elements is chosen at runtime, with `element_type`.
+    /// It is equivalent to calling the matching `Memory.*_view`
+    /// method.
+    ///
+    /// `element_type` is either a string among `'u8'`, `'i8'`,
+    /// `'u16'`, `'i16'`, `'u32'`, `'i32'`, `'u64'`, `'i64'`, `'f32'`
+    /// and `'f64'`, or one of `Type.I32`, `Type.I64`, `Type.F32` and
+    /// `Type.F64`. Any other element type raises a `ValueError`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType, Type, Int32Array, Float64Array
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1))
+    ///
+    /// assert isinstance(memory.view('i32'), Int32Array)
+    /// assert isinstance(memory.view(Type.F64, offset=8), Float64Array)
+    /// ```
+    #[pyo3(text_signature = "($self, element_type, /, offset=0)")]
+    #[args(offset = 0)]
+    fn view(&self, py: Python, element_type: &PyAny, offset: usize) -> PyResult<PyObject> {
+        let element_type = Self::element_type_name(element_type)?;
+
+        Ok(match element_type {
+            "u8" => Py::new(py, self.uint8_view(offset))?.to_object(py),
+            "i8" => Py::new(py, self.int8_view(offset))?.to_object(py),
+            "u16" => Py::new(py, self.uint16_view(offset))?.to_object(py),
+            "i16" => Py::new(py, self.int16_view(offset))?.to_object(py),
+            "u32" => Py::new(py, self.uint32_view(offset))?.to_object(py),
+            "i32" => Py::new(py, self.int32_view(offset))?.to_object(py),
+            "u64" => Py::new(py, self.uint64_view(offset))?.to_object(py),
+            "i64" => Py::new(py, self.int64_view(offset))?.to_object(py),
+            "f32" => Py::new(py, self.float32_view(offset))?.to_object(py),
+            "f64" => Py::new(py, self.float64_view(offset))?.to_object(py),
+            _ => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "There is no memory view for the element type `{}`",
+                    element_type
+                )))
+            }
+        })
+    }
+
+    /// Writes `values`, a list of numbers, contiguously in the
+    /// memory, starting at `offset` (in bytes). The values are packed
+    /// in little-endian according to `element_type`, which is
+    /// specified like for `Memory.view`, e.g. `'i32'` or `Type.F64`.
+    /// It returns the number of bytes written.
+    ///
+    /// All the values are checked before the memory is modified: a
+    /// `ValueError` is raised if a value can't be represented by the
+    /// element type, e.g. `-1` as `'u8'`, and an `IndexError` if the
+    /// values don't fit in the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1))
+    ///
+    /// assert memory.write_values(8, [1, 2, 3], 'i32') == 12
+    /// assert memory.int32_view(offset=2)[0:3] == [1, 2, 3]
+    /// ```
+    #[pyo3(text_signature = "($self, offset, values, element_type)")]
+    fn write_values(
+        &self,
+        offset: usize,
+        values: Vec<&PyAny>,
+        element_type: &PyAny,
+    ) -> PyResult<usize> {
+        let element_type = Self::element_type_name(element_type)?;
+        let mut bytes = Vec::new();
+
+        macro_rules! pack {
+            ($native_type:ty) => {
+                for (index, value) in values.iter().enumerate() {
+                    let value = value.extract::<$native_type>().map_err(|error| {
+                        to_py_err::<PyValueError, _>(format!(
+                            "The value at index {} cannot be written as `{}`: {}",
+                            index,
+                            element_type,
+                            error.pvalue(value.py())
+                        ))
+                    })?;
+
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            };
+        }
+
+        match element_type {
+            "u8" => pack!(u8),
+            "i8" => pack!(i8),
+            "u16" => pack!(u16),
+            "i16" => pack!(i16),
+            "u32" => pack!(u32),
+            "i32" => pack!(i32),
+            "u64" => pack!(u64),
+            "i64" => pack!(i64),
+            "f32" => pack!(f32),
+            "f64" => pack!(f64),
+            _ => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "There is no memory view for the element type `{}`",
+                    element_type
+                )))
+            }
+        }
+
+        let view = self.inner.view::<u8>();
+
+        let end = match offset.checked_add(bytes.len()) {
+            Some(end) if end <= view.len() => end,
+            _ => {
+                return Err(to_py_err::<PyIndexError, _>(format!(
+                    "Out of bound: writing {} bytes at offset {} overflows the memory of {} bytes",
+                    bytes.len(),
+                    offset,
+                    view.len()
+                )))
+            }
+        };
+
+        for (cell, byte) in view[offset..end].iter().zip(&bytes) {
+            cell.set(*byte);
+        }
+
+        Ok(bytes.len())
+    }
+
+    /// Creates a typed array over the memory data, starting at
+    /// `offset` (in bytes) and containing `count` elements of kind
+    /// `dtype`. If `count` is omitted, the array spans until the end
+    /// of the memory.
+    ///
+    /// Supported `dtype`s are `uint8`, `int8`, `uint16`, `int16`,
+    /// `uint32`, `int32`, `uint64`, `int64`, `float32` and `float64`.
+    ///
+    /// If [numpy](https://numpy.org/) is installed, a numpy array
+    /// aliasing the memory is returned (zero-copy): reads and writes
+    /// go to the memory directly. Otherwise, the data is copied, and
+    /// a `memoryview` over the copy is returned.
+    ///
+    /// An `IndexError` is raised if the array doesn't fit in the
+    /// memory.
+    ///
+    /// ## Important
+    ///
+    /// Growing the memory may move its data. A numpy array created
+    /// before `Memory.grow` (or before the WebAssembly module grows
+    /// the memory) is then invalid, and must not be used anymore:
+    /// create a new one instead.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1))
+    ///
+    /// int32 = memory.int32_view()
+    /// int32[1] = 42
+    ///
+    /// array = memory.as_numpy('int32', offset=4, count=2)
+    ///
+    /// assert list(array) == [42, 0]
+    /// ```
+    #[pyo3(text_signature = "($self, /, dtype='uint8', offset=0, count=None)")]
+    #[args(dtype = "\"uint8\"", offset = 0, count = "None")]
+    fn as_numpy(
+        &self,
+        py: Python,
+        dtype: &str,
+        offset: usize,
+        count: Option<usize>,
+    ) -> PyResult<PyObject> {
+        let (format, item_size) = match dtype {
+            "uint8" => ("B", 1),
+            "int8" => ("b", 1),
+            "uint16" => ("H", 2),
+            "int16" => ("h", 2),
+            "uint32" => ("I", 4),
+            "int32" => ("i", 4),
+            "uint64" => ("Q", 8),
+            "int64" => ("q", 8),
+            "float32" => ("f", 4),
+            "float64" => ("d", 8),
+            _ => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The dtype `{}` is not supported",
+                    dtype
+                )))
+            }
+        };
+        let data_size = self.inner.data_size() as usize;
+        let count = count.unwrap_or_else(|| data_size.saturating_sub(offset) / item_size);
+        let end = count
+            .checked_mul(item_size)
+            .and_then(|size| size.checked_add(offset))
+            .filter(|end| *end <= data_size)
+            .ok_or_else(|| {
+                to_py_err::<PyIndexError, _>(format!(
+                    "Out of bound: {} elements of kind `{}` at offset {} don't fit in the memory of {} bytes",
+                    count, dtype, offset, data_size
+                ))
+            })?;
+
+        match py.import("numpy") {
+            Ok(numpy) => Ok(numpy
+                .call_method(
+                    "frombuffer",
+                    (Py::new(py, self.buffer())?,),
+                    Some(
+                        [
+                            ("dtype", dtype.to_object(py)),
+                            ("count", count.to_object(py)),
+                            ("offset", offset.to_object(py)),
+                        ]
+                        .into_py_dict(py),
+                    ),
+                )?
+                .to_object(py)),
+
+            Err(_) => {
+                let bytes = self.inner.view::<u8>()[offset..end]
+                    .iter()
+                    .map(Cell::get)
+                    .collect::<Vec<u8>>();
+
+                Ok(py
+                    .import("builtins")?
+                    .getattr("memoryview")?
+                    .call1((PyBytes::new(py, &bytes),))?
+                    .call_method1("cast", (format,))?
+                    .to_object(py))
+            }
+        }
+    }
+
+    /// Reads a nul-terminated UTF-8 string from the memory, starting
+    /// at `offset` (in bytes). The string stops at the first nul
+    /// byte, after `max_length` bytes if given, or at the end of the
+    /// memory.
+    ///
+    /// `errors` is the policy applied to invalid UTF-8 bytes, like
+    /// for `bytes.decode`: `'strict'` raises a `UnicodeDecodeError`,
+    /// `'replace'` substitutes them with `U+FFFD`, and `'ignore'`
+    /// drops them.
+    ///
+    /// An `IndexError` is raised if `offset` is out of the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1), data=b'Wasmer\0\xffrocks\0')
+    ///
+    /// assert memory.read_cstring(0) == 'Wasmer'
+    /// assert memory.read_cstring(0, max_length=4) == 'Wasm'
+    /// assert memory.read_cstring(7, errors='replace') == '\ufffdrocks'
+    /// assert memory.read_cstring(7, errors='ignore') == 'rocks'
+    /// ```
+    #[pyo3(text_signature = "($self, offset, /, max_length=None, errors='strict')")]
+    #[args(max_length = "None", errors = "\"strict\"")]
+    fn read_cstring(
+        &self,
+        py: Python,
+        offset: usize,
+        max_length: Option<usize>,
+        errors: &str,
+    ) -> PyResult<PyObject> {
+        let view = self.inner.view::<u8>();
+
+        if offset > view.len() {
+            return Err(to_py_err::<PyIndexError, _>(format!(
+                "Out of bound: offset {} is outside the memory of {} bytes",
+                offset,
+                view.len()
+            )));
+        }
+
+        let end = max_length.map_or(view.len(), |max_length| {
+            offset.saturating_add(max_length).min(view.len())
+        });
+        let bytes = view[offset..end]
+            .iter()
+            .map(Cell::get)
+            .take_while(|byte| *byte != 0)
+            .collect::<Vec<u8>>();
+
+        Ok(PyBytes::new(py, &bytes)
+            .call_method1("decode", ("utf-8", errors))?
+            .to_object(py))
+    }
+
+    /// Copies bytes from the memory, starting at `offset` (in bytes),
+    /// into `buffer`, a writable object implementing the buffer
+    /// protocol with byte-sized items, e.g. a `bytearray`. Exactly
+    /// `len(buffer)` bytes are copied, and their number is returned.
+    ///
+    /// Contrary to reading a slice of `Memory.buffer`, no new `bytes`
+    /// object is allocated, which makes it suitable to read in a hot
+    /// loop, like `socket.recv_into`.
+    ///
+    /// A `ValueError` is raised if the bytes to copy overflow the
+    /// memory, and a `TypeError` if `buffer` isn't writable.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1), data=b'Hello, World!')
+    /// buffer = bytearray(5)
+    ///
+    /// assert memory.read_into(7, buffer) == 5
+    /// assert buffer == b'World'
+    /// ```
+    #[pyo3(text_signature = "($self, offset, buffer)")]
+    fn read_into(&self, py: Python, offset: usize, buffer: &PyAny) -> PyResult<usize> {
+        let buffer = PyBuffer::<u8>::get(buffer)?;
+
+        if buffer.readonly() {
+            return Err(to_py_err::<PyTypeError, _>("The buffer must be writable"));
+        }
+
+        // SAFETY: No WebAssembly code runs while the GIL is held by
+        // this method, so the memory is neither modified nor grown
+        // during the copy.
+        let data = unsafe { self.inner.data_unchecked() };
+        let length = buffer.item_count();
+
+        let end = match offset.checked_add(length) {
+            Some(end) if end <= data.len() => end,
+            _ => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "Out of bound: reading {} bytes at offset {} overflows the memory of {} bytes",
+                    length,
+                    offset,
+                    data.len()
+                )))
+            }
+        };
+
+        // The bytes are copied straight from the memory into the
+        // buffer, without an intermediate allocation.
+        buffer.copy_from_slice(py, &data[offset..end])?;
+
+        Ok(length)
+    }
+
+    /// Copies `data`, an object implementing the buffer protocol with
+    /// byte-sized items, e.g. `bytes` or `bytearray`, into the memory,
+    /// starting at `offset` (in bytes). It returns the number of
+    /// bytes written.
+    ///
+    /// If the data doesn't fit in the memory, and `grow` is `True`,
+    /// the memory is first grown to the number of pages needed to
+    /// hold `offset + len(data)` bytes. A `ValueError` is raised if
+    /// the data doesn't fit and `grow` is `False`, or if growing
+    /// would exceed the memory maximum.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1, maximum=4))
+    /// data = bytes(100000)
+    ///
+    /// assert memory.copy_from_bytes(65536, data, grow=True) == 100000
+    /// assert memory.size == 3
+    /// ```
+    #[pyo3(text_signature = "($self, offset, data, /, grow=False)")]
+    #[args(grow = false)]
+    fn copy_from_bytes(
+        &self,
+        py: Python,
+        offset: usize,
+        data: &PyAny,
+        grow: bool,
+    ) -> PyResult<usize> {
+        let bytes = PyBuffer::<u8>::get(data)?.to_vec(py)?;
+        let data_size = self.inner.view::<u8>().len();
+
+        let end = offset.checked_add(bytes.len()).ok_or_else(|| {
+            to_py_err::<PyValueError, _>(format!(
+                "Out of bound: writing {} bytes at offset {} overflows the address space",
+                bytes.len(),
+                offset
+            ))
+        })?;
+
+        if end > data_size {
+            if !grow {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "Out of bound: writing {} bytes at offset {} overflows the memory of {} bytes; pass `grow=True` to grow the memory",
+                    bytes.len(),
+                    offset,
+                    data_size
+                )));
+            }
+
+            let needed_pages = Self::pages_for_bytes(end as u64);
+            let maximum_pages = self
+                .inner
+                .ty()
+                .maximum
+                .map_or(wasmer::WASM_MAX_PAGES, |pages| pages.0);
+
+            if needed_pages > u64::from(maximum_pages) {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "Cannot grow the memory to {} pages to write {} bytes at offset {}: the maximum is {} pages",
+                    needed_pages,
+                    bytes.len(),
+                    offset,
+                    maximum_pages
+                )));
+            }
+
+            grow_from_host(|| self.inner.grow(needed_pages as u32 - self.inner.size().0))
+                .map_err(to_py_err::<PyValueError, _>)?;
+        }
+
+        let view = self.inner.view::<u8>();
+
+        for (cell, byte) in view[offset..end].iter().zip(&bytes) {
+            cell.set(*byte);
+        }
+
+        Ok(bytes.len())
+    }
+
+    /// Searches the memory for `pattern`, a `bytes`, within the range
+    /// `[start, end)` (in bytes), like `bytes.find`. It returns the
+    /// offset of the first occurrence, or -1 if there is none.
+    /// `end` defaults to the size of the memory, and is clamped to
+    /// it. Like with `bytes.find`, an empty `pattern` is found at
+    /// `start`, unless `start` is beyond `end`.
+    ///
+    /// The search runs natively over the memory data, without
+    /// copying it, with a vectorized substring search.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1), data=b'Hello, World!')
+    ///
+    /// assert memory.find(b'World') == 7
+    /// assert memory.find(b'Hello', start=1) == -1
+    /// assert memory.find(b'World', end=10) == -1
+    /// ```
+    #[pyo3(text_signature = "($self, pattern, /, start=0, end=None)")]
+    #[args(start = 0, end = "None")]
+    fn find(&self, pattern: &PyBytes, start: usize, end: Option<usize>) -> i64 {
+        let pattern = pattern.as_bytes();
+
+        // SAFETY: No WebAssembly code runs while the GIL is held by
+        // this method, so the memory is neither modified nor grown
+        // during the search.
+        let data = unsafe { self.inner.data_unchecked() };
+
+        let end = end.map_or(data.len(), |end| end.min(data.len()));
+
+        if start > end {
+            return -1;
+        }
+
+        if pattern.is_empty() {
+            return start as i64;
+        }
+
+        memchr::memmem::find(&data[start..end], pattern)
+            .map_or(-1, |position| (start + position) as i64)
+    }
+
     /// Gets the memory type, of kind `MemoryType`.
     ///
     /// ## Example
@@ -310,4 +1022,36 @@ impl Memory {
     fn ty(&self) -> MemoryType {
         self.inner.ty().into()
     }
+
+    /// The store this memory belongs to: the `Store` it has been
+    /// created with, or the store of the module of the instance
+    /// exporting it. It is `None` when the store isn't known, e.g.
+    /// for a memory read from an `ImportObject`.
+    ///
+    /// It allows to create new externs in the same store.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType, Global, Value
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1))
+    ///
+    /// assert memory.store is store
+    ///
+    /// global_ = Global(memory.store, Value.i32(42))
+    /// ```
+    #[getter]
+    fn store(&self, py: Python) -> Option<Py<Store>> {
+        self.store.as_ref().map(|store| store.clone_ref(py))
+    }
+
+    /// The identifier of the store this memory belongs to. See
+    /// `Store.id`. It is `None` when the store isn't known, see
+    /// `Memory.store`.
+    #[getter]
+    pub(crate) fn store_id(&self, py: Python) -> Option<u64> {
+        self.store.as_ref().map(|store| store.borrow(py).id())
+    }
 }
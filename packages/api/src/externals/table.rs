@@ -1,7 +1,12 @@
 use crate::{
-    errors::to_py_err, store::Store, types::TableType, values::Value, wasmer_inner::wasmer,
+    errors::to_py_err, externals::Function, store::Store, types::TableType, values::Value,
+    wasmer_inner::wasmer,
+};
+use pyo3::{
+    class::{mapping::PyMappingProtocol, sequence::PySequenceProtocol},
+    exceptions::{PyIndexError, PyRuntimeError, PyTypeError},
+    prelude::*,
 };
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
 
 /// A WebAssembly table instance.
 ///
@@ -12,34 +17,108 @@ use pyo3::{exceptions::PyRuntimeError, prelude::*};
 /// accessible and mutable from both host and WebAssembly.
 ///
 /// Specification: https://webassembly.github.io/spec/core/exec/runtime.html#table-instances
+///
+/// ## Example
+///
+/// A table implements `len` and can be indexed to read its elements,
+/// as `Value` objects, or `None` for null references:
+///
+/// ```py
+/// from wasmer import Store, Module, Instance, Value
+///
+/// module = Module(
+///     Store(),
+///     """
+///     (module
+///       (func $f)
+///       (table (export "table") 2 funcref)
+///       (elem (i32.const 0) $f))
+///     """
+/// )
+/// instance = Instance(module)
+/// table = instance.exports.table
+///
+/// assert len(table) == 2
+/// assert isinstance(table[0], Value)
+/// assert table[1] == None
+/// ```
 #[pyclass(unsendable)]
 #[pyo3(text_signature = "(store, table_type, initial_value)")]
 pub struct Table {
     inner: wasmer::Table,
+
+    // The Python store the table belongs to, if known, see
+    // `Table.store`.
+    store: Option<Py<Store>>,
 }
 
 impl Table {
     pub fn raw_new(inner: wasmer::Table) -> Self {
-        Self { inner }
+        Self { inner, store: None }
+    }
+
+    /// Attaches the Python `Store` the extern belongs to, see the
+    /// `store` getter.
+    pub(crate) fn with_store(self, store: Option<Py<Store>>) -> Self {
+        Self { store, ..self }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::Table {
         &self.inner
     }
+
+    fn check_index(&self, index: isize) -> PyResult<u32> {
+        let size = self.inner.size();
+
+        if index < 0 {
+            Err(to_py_err::<PyIndexError, _>(
+                "Out of bound: Index cannot be negative",
+            ))
+        } else if index >= size as isize {
+            Err(to_py_err::<PyIndexError, _>(format!(
+                "Out of bound: Index {} is larger than the table size {}",
+                index, size
+            )))
+        } else {
+            Ok(index as u32)
+        }
+    }
+
+    /// Converts a `Value` or a `Function` into a table element,
+    /// checking it matches the element type of the table.
+    fn to_element(&self, value: &PyAny) -> PyResult<wasmer::Value> {
+        let value = if let Ok(function) = value.downcast::<PyCell<Function>>() {
+            function.borrow().to_funcref()?
+        } else {
+            value.extract::<PyRef<Value>>()?.inner().clone()
+        };
+
+        let expected_type = self.inner.ty().ty;
+
+        if value.ty() != expected_type {
+            return Err(to_py_err::<PyTypeError, _>(format!(
+                "Cannot set a value of type `{:?}` in a table of `{:?}` elements",
+                value.ty(),
+                expected_type
+            )));
+        }
+
+        Ok(value)
+    }
 }
 
 #[pymethods]
 impl Table {
     #[new]
-    fn new(store: &Store, table_type: &TableType, initial_value: &Value) -> PyResult<Self> {
-        Ok(Self {
-            inner: wasmer::Table::new(
-                store.inner(),
-                table_type.into(),
-                initial_value.inner().clone(),
-            )
-            .map_err(to_py_err::<PyRuntimeError, _>)?,
-        })
+    fn new(store: &PyCell<Store>, table_type: &TableType, initial_value: &Value) -> PyResult<Self> {
+        let table = wasmer::Table::new(
+            store.borrow().inner(),
+            table_type.into(),
+            initial_value.inner().clone(),
+        )
+        .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        Ok(Self::raw_new(table).with_store(Some(store.into())))
     }
 
     /// Gets the table size (in elements).
@@ -80,4 +159,167 @@ impl Table {
     fn ty(&self) -> TableType {
         self.inner.ty().into()
     }
+
+    /// Sets the element at `index`. The value is either a `Value` or,
+    /// as a shorthand for a `funcref` value, a `Function`. A host
+    /// function must be imported by an instance first, and the
+    /// function exported by the instance be used instead.
+    ///
+    /// The value must match the element type of the table, i.e. a
+    /// `funcref` value for a `funcref` table, and an `externref`
+    /// value for an `externref` table, otherwise a `TypeError` is
+    /// raised. It is also possible to write `table[index] = value`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Function
+    ///
+    /// def answer() -> int:
+    ///     return 42
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (type $answer_t (func (result i32)))
+    ///       (import "env" "answer" (func $answer (type $answer_t)))
+    ///       (export "answer" (func $answer))
+    ///       (table (export "table") 1 funcref)
+    ///       (func (export "call") (result i32)
+    ///         (call_indirect (type $answer_t) (i32.const 0))))
+    ///     """
+    /// )
+    /// instance = Instance(module, {"env": {"answer": Function(store, answer)}})
+    ///
+    /// instance.exports.table.set(0, instance.exports.answer)
+    ///
+    /// assert instance.exports.call() == 42
+    /// ```
+    #[pyo3(text_signature = "($self, index, value)")]
+    fn set(&self, index: isize, value: &PyAny) -> PyResult<()> {
+        let index = self.check_index(index)?;
+        let value = self.to_element(value)?;
+
+        self.inner
+            .set(index, value)
+            .map_err(to_py_err::<PyRuntimeError, _>)
+    }
+
+    /// Sets the elements starting at `start` from a list of values.
+    /// Each value is either a `Value` or a `Function`, like in
+    /// `Table.set`.
+    ///
+    /// All the values are checked before the table is modified: an
+    /// `IndexError` is raised if they don't fit in the table, and a
+    /// `TypeError` if one of them doesn't match the element type of
+    /// the table.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (type $t (func (result i32)))
+    ///       (table (export "table") 2 funcref)
+    ///       (func (export "one") (result i32) i32.const 1)
+    ///       (func (export "two") (result i32) i32.const 2)
+    ///       (func (export "call") (param i32) (result i32)
+    ///         (call_indirect (type $t) (local.get 0))))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// exports = instance.exports
+    ///
+    /// exports.table.set_range(0, [exports.one, exports.two])
+    ///
+    /// assert exports.call(0) == 1
+    /// assert exports.call(1) == 2
+    /// ```
+    #[pyo3(text_signature = "($self, start, values)")]
+    fn set_range(&self, start: isize, values: Vec<&PyAny>) -> PyResult<()> {
+        let size = self.inner.size();
+
+        if start < 0 {
+            return Err(to_py_err::<PyIndexError, _>(
+                "Out of bound: Start cannot be negative",
+            ));
+        }
+
+        if start as usize + values.len() > size as usize {
+            return Err(to_py_err::<PyIndexError, _>(format!(
+                "Out of bound: {} values starting at {} don't fit in the table size {}",
+                values.len(),
+                start,
+                size
+            )));
+        }
+
+        let values = values
+            .into_iter()
+            .map(|value| self.to_element(value))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        for (index, value) in (start as u32..).zip(values) {
+            self.inner
+                .set(index, value)
+                .map_err(to_py_err::<PyRuntimeError, _>)?;
+        }
+
+        Ok(())
+    }
+
+    /// The store this table belongs to: the `Store` it has been
+    /// created with, or the store of the module of the instance
+    /// exporting it. It is `None` when the store isn't known, e.g.
+    /// for a table read from an `ImportObject`.
+    ///
+    /// ## Example
+    ///
+    /// See `Memory.store`.
+    #[getter]
+    fn store(&self, py: Python) -> Option<Py<Store>> {
+        self.store.as_ref().map(|store| store.clone_ref(py))
+    }
+
+    /// The identifier of the store this table belongs to. See
+    /// `Store.id`. It is `None` when the store isn't known, see
+    /// `Table.store`.
+    #[getter]
+    pub(crate) fn store_id(&self, py: Python) -> Option<u64> {
+        self.store.as_ref().map(|store| store.borrow(py).id())
+    }
+}
+
+#[pyproto]
+impl PySequenceProtocol for Table {
+    fn __len__(&self) -> usize {
+        self.inner.size() as usize
+    }
+}
+
+#[pyproto]
+impl PyMappingProtocol for Table {
+    /// Returns the element at `index`, as a `Value`, or `None` if
+    /// the element is a null reference.
+    fn __getitem__(&self, index: isize) -> PyResult<Option<Value>> {
+        let index = self.check_index(index)?;
+
+        Ok(match self.inner.get(index) {
+            None | Some(wasmer::Value::FuncRef(None)) => None,
+            Some(wasmer::Value::ExternRef(extern_ref)) if extern_ref.is_null() => None,
+            Some(value) => Some(Value::raw_new(value)),
+        })
+    }
+
+    /// Sets the element at `index`, see `Table.set`.
+    fn __setitem__(&mut self, index: isize, value: &PyAny) -> PyResult<()> {
+        self.set(index, value)
+    }
 }
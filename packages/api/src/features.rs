@@ -0,0 +1,260 @@
+use crate::wasmer_inner::wasmer;
+use pyo3::{class::basic::PyObjectProtocol, prelude::*};
+
+/// The WebAssembly features, i.e. the WebAssembly proposals, that
+/// can be enabled on an engine. Each property is a boolean indicating
+/// whether a proposal is enabled.
+///
+/// The constructor takes the proposals as keyword arguments; the
+/// omitted ones get Wasmer's defaults. Features can be passed to an
+/// engine, like `engine.Universal(Compiler, features=features)`.
+///
+/// The features of a store are obtained with `Store.features`: it is
+/// a copy, so modifying it doesn't change the store.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import Store, Features
+///
+/// features = Features(threads=True)
+///
+/// assert features.threads == True
+/// assert features.simd == True
+///
+/// features.simd = False
+///
+/// assert features.simd == False
+///
+/// features = Store().features
+///
+/// assert isinstance(features, Features)
+/// assert features.bulk_memory == True
+/// ```
+#[pyclass]
+#[pyo3(
+    text_signature = "(threads=False, reference_types=True, simd=True, bulk_memory=True, multi_value=True, tail_call=False, module_linking=False, multi_memory=False, memory64=False, exceptions=False)"
+)]
+#[derive(Clone)]
+pub struct Features {
+    inner: wasmer::Features,
+}
+
+impl Features {
+    pub(crate) fn raw_new(inner: wasmer::Features) -> Self {
+        Self { inner }
+    }
+}
+
+#[pymethods]
+impl Features {
+    #[new]
+    #[args(
+        threads = "None",
+        reference_types = "None",
+        simd = "None",
+        bulk_memory = "None",
+        multi_value = "None",
+        tail_call = "None",
+        module_linking = "None",
+        multi_memory = "None",
+        memory64 = "None",
+        exceptions = "None"
+    )]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        threads: Option<bool>,
+        reference_types: Option<bool>,
+        simd: Option<bool>,
+        bulk_memory: Option<bool>,
+        multi_value: Option<bool>,
+        tail_call: Option<bool>,
+        module_linking: Option<bool>,
+        multi_memory: Option<bool>,
+        memory64: Option<bool>,
+        exceptions: Option<bool>,
+    ) -> Self {
+        let mut inner = wasmer::Features::default();
+
+        if let Some(threads) = threads {
+            inner.threads = threads;
+        }
+
+        if let Some(reference_types) = reference_types {
+            inner.reference_types = reference_types;
+        }
+
+        if let Some(simd) = simd {
+            inner.simd = simd;
+        }
+
+        if let Some(bulk_memory) = bulk_memory {
+            inner.bulk_memory = bulk_memory;
+        }
+
+        if let Some(multi_value) = multi_value {
+            inner.multi_value = multi_value;
+        }
+
+        if let Some(tail_call) = tail_call {
+            inner.tail_call = tail_call;
+        }
+
+        if let Some(module_linking) = module_linking {
+            inner.module_linking = module_linking;
+        }
+
+        if let Some(multi_memory) = multi_memory {
+            inner.multi_memory = multi_memory;
+        }
+
+        if let Some(memory64) = memory64 {
+            inner.memory64 = memory64;
+        }
+
+        if let Some(exceptions) = exceptions {
+            inner.exceptions = exceptions;
+        }
+
+        Self { inner }
+    }
+
+    /// Whether the threads proposal is enabled.
+    #[getter]
+    fn threads(&self) -> bool {
+        self.inner.threads
+    }
+
+    #[setter]
+    fn set_threads(&mut self, value: bool) {
+        self.inner.threads = value;
+    }
+
+    /// Whether the reference types proposal is enabled.
+    #[getter]
+    fn reference_types(&self) -> bool {
+        self.inner.reference_types
+    }
+
+    #[setter]
+    fn set_reference_types(&mut self, value: bool) {
+        self.inner.reference_types = value;
+    }
+
+    /// Whether the SIMD proposal is enabled.
+    #[getter]
+    fn simd(&self) -> bool {
+        self.inner.simd
+    }
+
+    #[setter]
+    fn set_simd(&mut self, value: bool) {
+        self.inner.simd = value;
+    }
+
+    /// Whether the bulk memory proposal is enabled.
+    #[getter]
+    fn bulk_memory(&self) -> bool {
+        self.inner.bulk_memory
+    }
+
+    #[setter]
+    fn set_bulk_memory(&mut self, value: bool) {
+        self.inner.bulk_memory = value;
+    }
+
+    /// Whether the multi-value proposal is enabled.
+    #[getter]
+    fn multi_value(&self) -> bool {
+        self.inner.multi_value
+    }
+
+    #[setter]
+    fn set_multi_value(&mut self, value: bool) {
+        self.inner.multi_value = value;
+    }
+
+    /// Whether the tail call proposal is enabled.
+    #[getter]
+    fn tail_call(&self) -> bool {
+        self.inner.tail_call
+    }
+
+    #[setter]
+    fn set_tail_call(&mut self, value: bool) {
+        self.inner.tail_call = value;
+    }
+
+    /// Whether the module linking proposal is enabled.
+    #[getter]
+    fn module_linking(&self) -> bool {
+        self.inner.module_linking
+    }
+
+    #[setter]
+    fn set_module_linking(&mut self, value: bool) {
+        self.inner.module_linking = value;
+    }
+
+    /// Whether the multi-memory proposal is enabled.
+    #[getter]
+    fn multi_memory(&self) -> bool {
+        self.inner.multi_memory
+    }
+
+    #[setter]
+    fn set_multi_memory(&mut self, value: bool) {
+        self.inner.multi_memory = value;
+    }
+
+    /// Whether the 64-bit memory proposal is enabled.
+    #[getter]
+    fn memory64(&self) -> bool {
+        self.inner.memory64
+    }
+
+    #[setter]
+    fn set_memory64(&mut self, value: bool) {
+        self.inner.memory64 = value;
+    }
+
+    /// Whether the exceptions proposal is enabled.
+    #[getter]
+    fn exceptions(&self) -> bool {
+        self.inner.exceptions
+    }
+
+    #[setter]
+    fn set_exceptions(&mut self, value: bool) {
+        self.inner.exceptions = value;
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for Features {
+    fn __repr__(&self) -> String {
+        let features = &self.inner;
+
+        format!(
+            "Features(threads={}, reference_types={}, simd={}, bulk_memory={}, multi_value={}, tail_call={}, module_linking={}, multi_memory={}, memory64={}, exceptions={})",
+            py_bool(features.threads),
+            py_bool(features.reference_types),
+            py_bool(features.simd),
+            py_bool(features.bulk_memory),
+            py_bool(features.multi_value),
+            py_bool(features.tail_call),
+            py_bool(features.module_linking),
+            py_bool(features.multi_memory),
+            py_bool(features.memory64),
+            py_bool(features.exceptions),
+        )
+    }
+}
+
+fn py_bool(value: bool) -> &'static str {
+    if value {
+        "True"
+    } else {
+        "False"
+    }
+}
@@ -1,13 +1,18 @@
 use crate::{
-    errors::to_py_err,
+    errors::{to_py_err, TrapHandler},
+    exports::extern_to_py_object,
     externals::{Function, Global, Memory, Table},
+    module::Module,
+    store::Store,
     wasmer_inner::wasmer,
 };
 use pyo3::{
-    exceptions::PyTypeError,
+    class::iter::PyIterProtocol,
+    exceptions::{PyRuntimeError, PyTypeError},
     prelude::*,
     types::{PyDict, PyString},
 };
+use std::collections::BTreeMap;
 
 /// An `ImportObject` represents all of the import data used when
 /// instantiating a WebAssembly module.
@@ -147,20 +152,44 @@ use pyo3::{
 /// ```
 ///
 /// etc.
+///
+/// The `ImportObject` class implements [the Iterator
+/// Protocol](https://docs.python.org/3/c-api/iter.html), yielding
+/// `(namespace, name, extern)` tuples. Please see the
+/// `ImportObjectIterator` class.
 #[pyclass(unsendable)]
 #[pyo3(text_signature = "()")]
 pub struct ImportObject {
     inner: wasmer::ImportObject,
+
+    // The maximum number of pages of the memories of the instances
+    // created with the import object, if limited, see
+    // `wasi.StateBuilder.limits`.
+    max_memory_pages: Option<u32>,
 }
 
 impl ImportObject {
     pub(crate) fn raw_new(inner: wasmer::ImportObject) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            max_memory_pages: None,
+        }
     }
 
     pub(crate) fn inner(&self) -> &wasmer::ImportObject {
         &self.inner
     }
+
+    pub(crate) fn with_max_memory_pages(self, max_memory_pages: Option<u32>) -> Self {
+        Self {
+            max_memory_pages,
+            ..self
+        }
+    }
+
+    pub(crate) fn max_memory_pages(&self) -> Option<u32> {
+        self.max_memory_pages
+    }
 }
 
 impl ImportObject {
@@ -174,6 +203,58 @@ impl ImportObject {
         }
         Ok(io)
     }
+
+    /// Builds a stub for a single import, given its type.
+    fn stub(
+        store: &wasmer::Store,
+        namespace: &str,
+        name: &str,
+        ty: &wasmer::ExternType,
+    ) -> PyResult<wasmer::Extern> {
+        let null_value = |ty: wasmer::Type| match ty {
+            wasmer::Type::I32 => wasmer::Value::I32(0),
+            wasmer::Type::I64 => wasmer::Value::I64(0),
+            wasmer::Type::F32 => wasmer::Value::F32(0.0),
+            wasmer::Type::F64 => wasmer::Value::F64(0.0),
+            wasmer::Type::V128 => wasmer::Value::V128(0),
+            wasmer::Type::ExternRef => wasmer::Value::null(),
+            wasmer::Type::FuncRef => wasmer::Value::FuncRef(None),
+        };
+
+        Ok(match ty {
+            wasmer::ExternType::Function(function_type) => {
+                let message = format!(
+                    "The imported function `{}.{}` is a stub and cannot be called",
+                    namespace, name
+                );
+
+                wasmer::Function::new(store, function_type, move |_arguments| {
+                    Err(wasmer::RuntimeError::new(message.clone()))
+                })
+                .into()
+            }
+
+            wasmer::ExternType::Global(global_type) => {
+                let value = null_value(global_type.ty);
+
+                match global_type.mutability {
+                    wasmer::Mutability::Var => wasmer::Global::new_mut(store, value),
+                    wasmer::Mutability::Const => wasmer::Global::new(store, value),
+                }
+                .into()
+            }
+
+            wasmer::ExternType::Memory(memory_type) => wasmer::Memory::new(store, *memory_type)
+                .map_err(to_py_err::<PyRuntimeError, _>)?
+                .into(),
+
+            wasmer::ExternType::Table(table_type) => {
+                wasmer::Table::new(store, *table_type, null_value(table_type.ty))
+                    .map_err(to_py_err::<PyRuntimeError, _>)?
+                    .into()
+            }
+        })
+    }
 }
 
 #[pymethods]
@@ -199,6 +280,117 @@ impl ImportObject {
         self.inner.contains_namespace(namespace_name)
     }
 
+    /// Creates an `ImportObject` pre-filled with stubs matching all
+    /// the imports of a module, so that it can be instantiated right
+    /// away. Real implementations can be registered later on, in
+    /// place of the stubs.
+    ///
+    /// Each imported function is stubbed by a function with the same
+    /// signature that traps when called, with a message naming the
+    /// import. Imported globals are initialized to zero (or null for
+    /// references), imported memories and tables are created with
+    /// their minimum size.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, ImportObject
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (import "math" "sum" (func $sum (param i32 i32) (result i32)))
+    ///       (import "env" "memory" (memory 1))
+    ///       (func (export "add_one") (param i32) (result i32)
+    ///         local.get 0
+    ///         i32.const 1
+    ///         call $sum))
+    ///     """
+    /// )
+    ///
+    /// import_object = ImportObject.stubs_for(store, module)
+    /// instance = Instance(module, import_object)
+    ///
+    /// try:
+    ///     instance.exports.add_one(1)
+    /// except RuntimeError as error:
+    ///     assert "math.sum" in str(error)
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(store, module)")]
+    fn stubs_for(store: &Store, module: &Module) -> PyResult<Self> {
+        let mut namespaces: BTreeMap<String, wasmer::Exports> = BTreeMap::new();
+
+        for import in module.inner().imports() {
+            let stub = Self::stub(store.inner(), import.module(), import.name(), import.ty())?;
+
+            namespaces
+                .entry(import.module().to_string())
+                .or_default()
+                .insert(import.name(), stub);
+        }
+
+        let mut import_object = ImportObject::new();
+
+        for (namespace_name, namespace) in namespaces {
+            import_object.inner.register(namespace_name, namespace);
+        }
+
+        Ok(import_object)
+    }
+
+    /// Returns an independent copy of the import object: namespaces
+    /// registered in the copy don't affect the original, and
+    /// conversely. The imports themselves, e.g. the `Function` or
+    /// `Memory` objects, are shared.
+    ///
+    /// It allows to build a base import object with common host
+    /// functions, and to derive a variant per instance.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, ImportObject, Function
+    ///
+    /// store = Store()
+    ///
+    /// def sum(x: int, y: int) -> int:
+    ///     return x + y
+    ///
+    /// base = ImportObject()
+    /// base.register("math", {"sum": Function(store, sum)})
+    ///
+    /// import_object = base.clone()
+    /// import_object.register("env", {"sum": Function(store, sum)})
+    ///
+    /// assert import_object.contains_namespace("math")
+    /// assert import_object.contains_namespace("env")
+    /// assert not base.contains_namespace("env")
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn clone(&self) -> Self {
+        // `wasmer::ImportObject::clone` shares the namespaces with
+        // the original, so they are copied one by one instead.
+        let mut namespaces: BTreeMap<String, wasmer::Exports> = BTreeMap::new();
+
+        for (namespace_name, name, export) in self.inner.externs_vec() {
+            namespaces
+                .entry(namespace_name)
+                .or_default()
+                .insert(name, export);
+        }
+
+        let mut import_object = ImportObject::new();
+
+        for (namespace_name, namespace) in namespaces {
+            import_object.inner.register(namespace_name, namespace);
+        }
+
+        import_object.with_max_memory_pages(self.max_memory_pages)
+    }
+
     /// Gets a Python dictionary from an `ImportObject`.
     #[pyo3(text_signature = "($self)")]
     pub(crate) fn to_dict<'py>(&'py self) -> Result<PyObject, PyErr> {
@@ -207,20 +399,7 @@ impl ImportObject {
 
         let dict = PyDict::new(py);
         for (namespace, name, export) in self.inner.externs_vec() {
-            let elem = match export {
-                wasmer::Extern::Function(function) => {
-                    Py::new(py, Function::raw_new(function.clone()))?.to_object(py)
-                }
-                wasmer::Extern::Global(global) => {
-                    Py::new(py, Global::raw_new(global.clone()))?.to_object(py)
-                }
-                wasmer::Extern::Memory(memory) => {
-                    Py::new(py, Memory::raw_new(memory.clone()))?.to_object(py)
-                }
-                wasmer::Extern::Table(table) => {
-                    Py::new(py, Table::raw_new(table.clone()))?.to_object(py)
-                }
-            };
+            let elem = extern_to_py_object(py, &export, &TrapHandler::default(), None, None)?;
             let namespace_dict = match dict.get_item(&namespace) {
                 Some(namespace_dict) => namespace_dict,
                 None => {
@@ -292,3 +471,74 @@ impl ImportObject {
         Ok(())
     }
 }
+
+#[pyproto]
+impl PyIterProtocol for ImportObject {
+    fn __iter__(slf: PyRef<Self>) -> ImportObjectIterator {
+        ImportObjectIterator {
+            vector: slf.inner.externs_vec(),
+            index: 0,
+        }
+    }
+}
+
+/// Iterator over all the imports of an `ImportObject`, as
+/// `(namespace, name, extern)` tuples where `extern` is a `Function`,
+/// a `Global`, a `Memory` or a `Table`.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import Store, ImportObject, Function, Memory, MemoryType
+///
+/// store = Store()
+///
+/// def sum(x: int, y: int) -> int:
+///     return x + y
+///
+/// import_object = ImportObject()
+/// import_object.register(
+///     "env",
+///     {
+///         "sum": Function(store, sum),
+///     }
+/// )
+///
+/// for (namespace, name, extern) in import_object:
+///     assert (namespace, name) == ("env", "sum")
+///     assert isinstance(extern, Function)
+/// ```
+#[pyclass(unsendable)]
+pub struct ImportObjectIterator {
+    vector: Vec<(String, String, wasmer::Extern)>,
+    index: usize,
+}
+
+#[pyproto]
+impl PyIterProtocol for ImportObjectIterator {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<Self>) -> PyResult<Option<(String, String, PyObject)>> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        let (output, next_index) = match slf.vector.get(slf.index) {
+            Some((namespace, name, export)) => (
+                Ok(Some((
+                    namespace.clone(),
+                    name.clone(),
+                    extern_to_py_object(py, export, &TrapHandler::default(), None, None)?,
+                ))),
+                slf.index + 1,
+            ),
+
+            None => (Ok(None), slf.index),
+        };
+
+        slf.index = next_index;
+
+        output
+    }
+}
@@ -1,11 +1,22 @@
 use crate::errors::runtime_error_to_py_err;
 use crate::{
-    errors::to_py_err, exports::Exports, import_object::ImportObject, module::Module,
+    errors::{to_py_err, InstantiationError, LinkError, TrapHandler},
+    exports::{extern_to_py_object, Exports},
+    externals::Function,
+    import_object::ImportObject,
+    interruption::InterruptionHandle,
+    module::Module,
+    store::Store,
     wasmer_inner::wasmer,
 };
-use pyo3::types::PyDict;
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
-use std::borrow::Borrow;
+use pyo3::types::{PyBytes, PyDict, PyString, PyTuple};
+use pyo3::{
+    buffer::PyBuffer,
+    class::basic::PyObjectProtocol,
+    exceptions::{PyAttributeError, PyLookupError, PyRuntimeError, PyValueError},
+    prelude::*,
+};
+use std::{borrow::Borrow, cell::Cell, convert::TryFrom};
 
 /// A WebAssembly instance is a stateful, executable instance of a
 /// WebAssembly `Module`.
@@ -81,10 +92,31 @@ use std::borrow::Borrow;
 /// # Let's test it!
 /// assert instance.exports.add_one(41) == 42
 /// ```
+///
+/// The memories defined by the module can be capped with
+/// `max_memory_pages`, whatever their declared maximum is. Growing
+/// them beyond the cap from WebAssembly traps, i.e. the call raises
+/// a `TrapError` (growing them beyond their declared maximum still
+/// makes `memory.grow` return -1), and `Memory.grow` raises a
+/// `RuntimeError` from Python. Imported memories aren't affected.
+///
+/// ```py
+/// from wasmer import Store, Module, Instance
+///
+/// module = Module(Store(), '(module (memory (export "memory") 1 100))')
+/// instance = Instance(module, max_memory_pages=2)
+///
+/// memory = instance.exports.memory
+/// memory.grow(1)
+///
+/// try:
+///     memory.grow(1)
+/// except RuntimeError:
+///     pass
+/// ```
 #[pyclass(unsendable)]
-#[pyo3(text_signature = "(module, import_object)")]
+#[pyo3(text_signature = "(module, import_object, /, max_memory_pages=None)")]
 pub struct Instance {
-    #[allow(unused)]
     inner: wasmer::Instance,
 
     /// The exports of the instance, as an object of kind `Exports`.
@@ -94,6 +126,14 @@ pub struct Instance {
     /// See the `Exports` class.
     #[pyo3(get)]
     exports: Py<Exports>,
+
+    trap_handler: TrapHandler,
+
+    // The Python store of the module, see `Memory.store`.
+    store: Py<Store>,
+
+    // See `InterruptionHandle`.
+    interruption_handle: Option<InterruptionHandle>,
 }
 
 pub enum InstanceError {
@@ -106,8 +146,35 @@ impl Instance {
         py: Python,
         module: &Module,
         import_object: Option<&PyAny>,
+        max_memory_pages: Option<u32>,
     ) -> Result<Self, InstanceError> {
-        let module = module.inner();
+        let store = module.store().clone_ref(py);
+
+        // An import object may limit the memories too, see
+        // `wasi.StateBuilder.limits`; the lowest limit wins.
+        let import_object_max_memory_pages = import_object
+            .and_then(|import_object| import_object.downcast::<PyCell<ImportObject>>().ok())
+            .and_then(|import_object| import_object.borrow().max_memory_pages());
+        let max_memory_pages = match (max_memory_pages, import_object_max_memory_pages) {
+            (Some(max_memory_pages), Some(import_object_max_memory_pages)) => {
+                Some(max_memory_pages.min(import_object_max_memory_pages))
+            }
+            (max_memory_pages, import_object_max_memory_pages) => {
+                max_memory_pages.or(import_object_max_memory_pages)
+            }
+        };
+
+        let limited_module;
+        let module = match max_memory_pages {
+            Some(max_memory_pages) => {
+                limited_module = module
+                    .limited(max_memory_pages)
+                    .map_err(InstanceError::PyErr)?;
+
+                &limited_module
+            }
+            None => module.inner(),
+        };
 
         let instance = match import_object {
             Some(import_object) => match import_object.downcast::<PyCell<ImportObject>>() {
@@ -125,31 +192,575 @@ impl Instance {
             },
             None => wasmer::Instance::new(&module, &wasmer::imports! {}),
         };
-        let instance = instance.map_err(InstanceError::InstantiationError)?;
+        let mut instance = instance.map_err(InstanceError::InstantiationError)?;
+
+        // The interruption flag is exported, but must not be seen
+        // from Python.
+        let interruption_handle = InterruptionHandle::of(&instance.exports);
+        instance.exports = InterruptionHandle::visible_exports(&instance.exports);
 
-        let exports =
-            Py::new(py, Exports::new(instance.exports.clone())).map_err(InstanceError::PyErr)?;
+        let trap_handler = TrapHandler::default();
+        let exports = Py::new(
+            py,
+            Exports::new(
+                instance.exports.clone(),
+                trap_handler.clone(),
+                Some(store.clone_ref(py)),
+                interruption_handle.clone(),
+            ),
+        )
+        .map_err(InstanceError::PyErr)?;
 
         Ok(Instance {
+            interruption_handle,
             inner: instance,
             exports,
+            trap_handler,
+            store,
         })
     }
+
+    /// Returns the exports satisfying `predicate` as Python objects,
+    /// in the export order.
+    fn exports_of_kind(
+        &self,
+        py: Python,
+        predicate: fn(&wasmer::Extern) -> bool,
+    ) -> PyResult<Vec<PyObject>> {
+        self.inner
+            .exports
+            .iter()
+            .filter(|(_, export)| predicate(export))
+            .map(|(_, export)| {
+                extern_to_py_object(
+                    py,
+                    export,
+                    &self.trap_handler,
+                    Some(&self.store),
+                    self.interruption_handle.as_ref(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the exported function named `name`, set up like the
+    /// functions of `Instance.exports`, so that calling it takes the
+    /// same path, see `Function.call_values`.
+    fn exported_function(&self, py: Python, name: &str) -> Option<Function> {
+        let function = self.inner.exports.get_function(name).ok()?;
+
+        Some(
+            Function::with_trap_handler(function.clone(), self.trap_handler.clone())
+                .with_store(Some(self.store.clone_ref(py)))
+                .with_interruption_handle(self.interruption_handle.clone()),
+        )
+    }
+
+    /// Returns the memory exported by the instance: the export named
+    /// `memory`, or the first exported memory otherwise.
+    fn exported_memory(&self) -> PyResult<&wasmer::Memory> {
+        self.inner
+            .exports
+            .get_memory("memory")
+            .ok()
+            .or_else(|| {
+                self.inner
+                    .exports
+                    .iter()
+                    .find_map(|(_, export)| match export {
+                        wasmer::Extern::Memory(memory) => Some(memory),
+                        _ => None,
+                    })
+            })
+            .ok_or_else(|| to_py_err::<PyRuntimeError, _>("The instance has no exported memory"))
+    }
+
+    /// Builds the error raised by `Instance.call` when there is no
+    /// exported function named `name`, suggesting the names of the
+    /// exported functions that are close to it.
+    fn missing_function_error(&self, name: &str) -> PyErr {
+        let function_names = self
+            .inner
+            .exports
+            .iter()
+            .filter(|(_, export)| matches!(export, wasmer::Extern::Function(_)))
+            .map(|(function_name, _)| function_name.as_str())
+            .collect::<Vec<_>>();
+
+        let max_distance = (name.chars().count() / 3).max(1);
+        let mut suggestions = function_names
+            .iter()
+            .map(|function_name| (edit_distance(name, function_name), *function_name))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .collect::<Vec<_>>();
+        suggestions.sort();
+
+        let mut message = if self.inner.exports.contains(name) {
+            format!("The export `{}` is not a function", name)
+        } else {
+            format!("The instance has no exported function `{}`", name)
+        };
+
+        if !suggestions.is_empty() {
+            message.push_str(&format!(
+                "; did you mean {}?",
+                suggestions
+                    .iter()
+                    .map(|(_, function_name)| format!("`{}`", function_name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        } else if !function_names.is_empty() {
+            message.push_str(&format!(
+                "; the exported functions are: {}",
+                function_names
+                    .iter()
+                    .map(|function_name| format!("`{}`", function_name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        to_py_err::<PyAttributeError, _>(message)
+    }
+}
+
+/// Computes the Levenshtein distance between `a` and `b`, i.e. the
+/// minimum number of single-character insertions, deletions and
+/// substitutions to transform one into the other.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut row = vec![i + 1; b.len() + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous_row[j] + usize::from(a_char != *b_char);
+
+            row[j + 1] = substitution.min(previous_row[j + 1] + 1).min(row[j] + 1);
+        }
+
+        previous_row = row;
+    }
+
+    previous_row[b.len()]
 }
 
 #[pymethods]
 impl Instance {
     #[new]
-    fn new(py: Python, module: &Module, import_object: Option<&PyAny>) -> PyResult<Self> {
-        Instance::raw_new(py, &module, import_object).map_err(|error| match error {
-            InstanceError::InstantiationError(error) => {
-                if let wasmer::InstantiationError::Start(error) = error {
-                    runtime_error_to_py_err(error)
-                } else {
-                    to_py_err::<PyRuntimeError, _>(error)
-                }
+    #[args(import_object = "None", max_memory_pages = "None")]
+    fn new(
+        py: Python,
+        module: &Module,
+        import_object: Option<&PyAny>,
+        max_memory_pages: Option<u32>,
+    ) -> PyResult<Self> {
+        Instance::raw_new(py, module, import_object, max_memory_pages).map_err(
+            |error| match error {
+                InstanceError::InstantiationError(error) => match error {
+                    wasmer::InstantiationError::Start(error) => runtime_error_to_py_err(error),
+                    wasmer::InstantiationError::Link(error) => to_py_err::<LinkError, _>(error),
+                    error => to_py_err::<InstantiationError, _>(error),
+                },
+                InstanceError::PyErr(error) => error,
+            },
+        )
+    }
+
+    /// The memories exported by the instance, as a list of `Memory`
+    /// objects, in the export order. Contrary to `Instance.exports`,
+    /// they are reached without knowing their export names.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Memory, Table, Global
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (memory (export "mem") 1)
+    ///       (table (export "tab") 1 funcref)
+    ///       (global (export "glob") i32 (i32.const 7)))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// assert isinstance(instance.memories[0], Memory)
+    /// assert isinstance(instance.tables[0], Table)
+    /// assert instance.globals[0].value == 7
+    /// ```
+    #[getter]
+    fn memories(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.exports_of_kind(py, |export| matches!(export, wasmer::Extern::Memory(_)))
+    }
+
+    /// The tables exported by the instance, as a list of `Table`
+    /// objects, in the export order. See `Instance.memories` to see
+    /// an example.
+    #[getter]
+    fn tables(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.exports_of_kind(py, |export| matches!(export, wasmer::Extern::Table(_)))
+    }
+
+    /// The globals exported by the instance, as a list of `Global`
+    /// objects, in the export order. See `Instance.memories` to see
+    /// an example.
+    #[getter]
+    fn globals(&self, py: Python) -> PyResult<Vec<PyObject>> {
+        self.exports_of_kind(py, |export| matches!(export, wasmer::Extern::Global(_)))
+    }
+
+    /// Reports whether the module's start function, i.e. its
+    /// `(start $f)` section, has been run.
+    ///
+    /// The start function is run once, when the instance is
+    /// created, as required by the WebAssembly specification: it
+    /// isn't run again. Consequently, this method returns `True` if
+    /// the module has a start function (that has already been run),
+    /// `False` otherwise. See `Module.has_start_function` to
+    /// introspect a module without instantiating it.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(Store(), '(module (func $f) (start $f))')
+    /// instance = Instance(module)
+    ///
+    /// assert instance.run_start_section() == True
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn run_start_section(&self) -> bool {
+        self.inner.module().info().start_function.is_some()
+    }
+
+    /// Calls the exported function named `name` with `arguments`,
+    /// like `instance.exports.<name>(*arguments)`, and returns its
+    /// results.
+    ///
+    /// An `AttributeError` is raised if there is no export named
+    /// `name`, or if it isn't a function. Its message suggests the
+    /// exported functions whose names are close to `name`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (func (export "sum") (param i32 i32) (result i32)
+    ///         local.get 0
+    ///         local.get 1
+    ///         i32.add))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// assert instance.call('sum', 1, 2) == 3
+    ///
+    /// try:
+    ///     instance.call('sun', 1, 2)
+    /// except AttributeError as error:
+    ///     assert 'did you mean `sum`?' in str(error)
+    /// ```
+    #[args(arguments = "*")]
+    #[pyo3(text_signature = "($self, name, *arguments)")]
+    fn call(&self, py: Python, name: &str, arguments: &PyTuple) -> PyResult<PyObject> {
+        let function = match self.inner.exports.get_extern(name) {
+            Some(export @ wasmer::Extern::Function(_)) => extern_to_py_object(
+                py,
+                export,
+                &self.trap_handler,
+                Some(&self.store),
+                self.interruption_handle.as_ref(),
+            )?,
+            _ => return Err(self.missing_function_error(name)),
+        };
+
+        function.call1(py, arguments)
+    }
+
+    /// Initializes a reactor module, i.e. a module that exports an
+    /// `_initialize` function (like WASI reactors) instead of a
+    /// `_start` function (like WASI commands), by calling it. It must
+    /// be called once, before any other exported function.
+    ///
+    /// A `LookupError` is raised if the module doesn't export an
+    /// `_initialize` function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (global $ready (mut i32) (i32.const 0))
+    ///       (func (export "_initialize")
+    ///         i32.const 1
+    ///         global.set $ready)
+    ///       (func (export "is_ready") (result i32)
+    ///         global.get $ready))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    /// instance.initialize()
+    ///
+    /// assert instance.exports.is_ready() == 1
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn initialize(&self, py: Python) -> PyResult<()> {
+        let initialize = self.exported_function(py, "_initialize").ok_or_else(|| {
+            to_py_err::<PyLookupError, _>(
+                "The instance has no `_initialize` function to call; it is not a reactor module",
+            )
+        })?;
+
+        initialize.call_values(py, &[])?;
+
+        Ok(())
+    }
+
+    /// Copies `data` (any object implementing the buffer protocol)
+    /// into the memory of the instance, and returns the `(pointer,
+    /// length)` pair to pass to a guest function expecting a buffer.
+    ///
+    /// The space is reserved by calling the exported `allocator`
+    /// function (`malloc` by default), which must take the length as
+    /// an `i32`, and return the pointer as an `i32`. The data is
+    /// written in the exported memory named `memory`, or in the
+    /// first exported memory otherwise.
+    ///
+    /// The caller is responsible for freeing the space, e.g. by
+    /// calling the guest's `free` function with the pointer.
+    ///
+    /// A `LookupError` is raised if the allocator doesn't exist, a
+    /// `RuntimeError` if the instance has no exported memory, and a
+    /// `ValueError` if the allocator returns an invalid pointer.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (memory (export "memory") 1)
+    ///       (global $next (mut i32) (i32.const 1024))
+    ///       (func (export "malloc") (param $length i32) (result i32)
+    ///         global.get $next
+    ///         global.get $next
+    ///         local.get $length
+    ///         i32.add
+    ///         global.set $next)
+    ///       (func (export "first_byte") (param $pointer i32) (param $length i32) (result i32)
+    ///         local.get $pointer
+    ///         i32.load8_u))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// (pointer, length) = instance.pass_bytes(b'Hello')
+    ///
+    /// assert (pointer, length) == (1024, 5)
+    /// assert instance.exports.first_byte(pointer, length) == ord('H')
+    /// ```
+    #[args(allocator = "\"malloc\"")]
+    #[pyo3(text_signature = "($self, data, allocator='malloc')")]
+    fn pass_bytes(&self, py: Python, data: &PyAny, allocator: &str) -> PyResult<(u32, u32)> {
+        let data = PyBuffer::<u8>::get(data)?.to_vec(py)?;
+        let length = i32::try_from(data.len()).map_err(|_| {
+            to_py_err::<PyValueError, _>(format!(
+                "The data is too large to be passed to the instance ({} bytes)",
+                data.len()
+            ))
+        })?;
+
+        let memory = self.exported_memory()?;
+        let allocate = self.exported_function(py, allocator).ok_or_else(|| {
+            to_py_err::<PyLookupError, _>(format!(
+                "The instance has no `{}` function to allocate memory",
+                allocator
+            ))
+        })?;
+
+        let results = allocate.call_values(py, &[wasmer::Value::I32(length)])?;
+        let pointer = match *results {
+            [wasmer::Value::I32(pointer)] => pointer as u32,
+            _ => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The `{}` function must return a single `i32` pointer",
+                    allocator
+                )))
             }
-            InstanceError::PyErr(error) => error,
-        })
+        };
+
+        let view = memory.view::<u8>();
+        let start = pointer as usize;
+        let end = start + data.len();
+
+        if end > view.len() {
+            return Err(to_py_err::<PyValueError, _>(format!(
+                "The `{}` function returned an out of bound pointer: writing {} bytes at {} overflows the memory of {} bytes",
+                allocator,
+                data.len(),
+                pointer,
+                view.len()
+            )));
+        }
+
+        for (cell, byte) in view[start..end].iter().zip(&data) {
+            cell.set(*byte);
+        }
+
+        Ok((pointer, length as u32))
+    }
+
+    /// Reads `length` bytes at `pointer` from the memory of the
+    /// instance, e.g. to read back a `(pointer, length)` pair
+    /// returned by a guest function. It is the counterpart of
+    /// `Instance.pass_bytes`, and reads the same memory.
+    ///
+    /// A `RuntimeError` is raised if the instance has no exported
+    /// memory, and a `ValueError` if the bytes overflow the memory.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance
+    ///
+    /// module = Module(
+    ///     Store(),
+    ///     """
+    ///     (module
+    ///       (memory (export "memory") 1)
+    ///       (data (i32.const 42) "Hello, World!"))
+    ///     """
+    /// )
+    /// instance = Instance(module)
+    ///
+    /// assert instance.read_bytes(42, 5) == b'Hello'
+    /// ```
+    #[pyo3(text_signature = "($self, pointer, length)")]
+    fn read_bytes<'py>(
+        &self,
+        py: Python<'py>,
+        pointer: usize,
+        length: usize,
+    ) -> PyResult<&'py PyBytes> {
+        let view = self.exported_memory()?.view::<u8>();
+
+        let end = match pointer.checked_add(length) {
+            Some(end) if end <= view.len() => end,
+            _ => {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "Out of bound: reading {} bytes at {} overflows the memory of {} bytes",
+                    length,
+                    pointer,
+                    view.len()
+                )))
+            }
+        };
+
+        let bytes = view[pointer..end]
+            .iter()
+            .map(Cell::get)
+            .collect::<Vec<u8>>();
+
+        Ok(PyBytes::new(py, &bytes))
+    }
+
+    /// Sets a handler that is called with the exception object each
+    /// time a call to an exported function traps because a host
+    /// function has raised an exception, before the exception is
+    /// re-raised to the caller. It gives a single place to log or
+    /// transform the errors crossing the WebAssembly boundary.
+    ///
+    /// The value returned by the handler is ignored. If the handler
+    /// raises an exception, it replaces the propagated one. Passing
+    /// `None` removes the handler.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, Instance, Function
+    ///
+    /// def fail():
+    ///     raise ValueError('oops')
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (import "env" "fail" (func $fail))
+    ///       (func (export "run") call $fail))
+    ///     """
+    /// )
+    /// instance = Instance(module, {"env": {"fail": Function(store, fail)}})
+    ///
+    /// errors = []
+    /// instance.set_trap_handler(errors.append)
+    ///
+    /// try:
+    ///     instance.exports.run()
+    /// except ValueError:
+    ///     pass
+    ///
+    /// assert str(errors[0]) == 'oops'
+    /// ```
+    #[pyo3(text_signature = "($self, callback)")]
+    fn set_trap_handler(&self, callback: Option<&PyAny>) -> PyResult<()> {
+        if let Some(callback) = callback {
+            if !callback.is_callable() {
+                return Err(to_py_err::<PyValueError, _>(
+                    "The trap handler must be a callable",
+                ));
+            }
+        }
+
+        *self
+            .trap_handler
+            .lock()
+            .map_err(to_py_err::<PyRuntimeError, _>)? = callback.map(Into::into);
+
+        Ok(())
+    }
+}
+
+/// The maximum number of export names displayed by `Instance.__repr__`.
+const REPR_MAX_EXPORTS: usize = 10;
+
+#[pyproto]
+impl PyObjectProtocol for Instance {
+    fn __repr__(&self) -> PyResult<String> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        let mut names = self
+            .inner
+            .exports
+            .iter()
+            .take(REPR_MAX_EXPORTS)
+            .map(|(name, _)| Ok(PyString::new(py, name).repr()?.to_str()?.to_string()))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        if self.inner.exports.len() > REPR_MAX_EXPORTS {
+            names.push("...".to_string());
+        }
+
+        Ok(format!("Instance(exports=[{}])", names.join(", ")))
     }
 }
@@ -0,0 +1,354 @@
+use crate::wasmer_inner::{wasmer, wasmer_engines::INTERRUPTED_GLOBAL};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicI32, AtomicU64, Ordering},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use wasmer::Exportable;
+
+/// How often the watcher thread checks whether `SIGINT` has been
+/// received during a call.
+const WATCH_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Allows to interrupt the WebAssembly code of an instance compiled by
+/// an interruptible engine (see the `interruptible` argument of
+/// `Store`), while it runs: its interruption flag, a global the code
+/// checks at the start of each function and loop iteration, is set,
+/// and the code traps on its next check.
+///
+/// A signal handler can't safely touch the globals of the instance,
+/// so the `SIGINT` handler installed during a call only records the
+/// signal, and forwards it to the Python handler; a watcher thread
+/// then interrupts the instances being called.
+#[derive(Clone)]
+pub(crate) struct InterruptionHandle {
+    interrupted: wasmer::Global,
+}
+
+impl InterruptionHandle {
+    /// Returns the handle of the instance with the given exports, if
+    /// it has been compiled by an interruptible engine.
+    pub(crate) fn of(exports: &wasmer::Exports) -> Option<Self> {
+        Some(Self {
+            interrupted: exports.get_global(INTERRUPTED_GLOBAL).ok()?.clone(),
+        })
+    }
+
+    /// Whether `name` is the name of the export holding the
+    /// interruption flag. It is hidden from the exports seen from
+    /// Python.
+    pub(crate) fn is_reserved(name: &str) -> bool {
+        name == INTERRUPTED_GLOBAL
+    }
+
+    /// Returns `exports` without the export holding the interruption
+    /// flag, see `InterruptionHandle::is_reserved`.
+    pub(crate) fn visible_exports(exports: &wasmer::Exports) -> wasmer::Exports {
+        let mut visible_exports = wasmer::Exports::new();
+
+        for (name, export) in exports.iter() {
+            if !Self::is_reserved(name) {
+                visible_exports.insert(name.clone(), export.clone());
+            }
+        }
+
+        visible_exports
+    }
+
+    /// Sets the interruption flag. The watcher thread sets it while
+    /// the instance runs on another thread, so it is written
+    /// atomically, instead of with `wasmer::Global::set`.
+    fn set_interrupted(&self, interrupted: bool) {
+        let definition = match self.interrupted.to_export() {
+            wasmer::Export::Global(global) => global.from.vmglobal(),
+            _ => unreachable!("The interruption flag is a global"),
+        };
+
+        // SAFETY: The definition is kept alive by `self.interrupted`.
+        // It is an `i32` global (see `INTERRUPTED_GLOBAL`), stored at
+        // the start of the suitably aligned definition, and the code
+        // only ever reads it.
+        let flag = unsafe { &*(definition.as_ptr() as *const AtomicI32) };
+
+        flag.store(i32::from(interrupted), Ordering::SeqCst);
+    }
+
+    /// Clears the interruption flag, so that the instance only traps
+    /// if it is interrupted.
+    fn reset(&self) {
+        self.set_interrupted(false);
+    }
+
+    fn interrupt(&self) {
+        self.set_interrupted(true);
+    }
+
+    /// Runs `call`, which calls a function of the instance, so that it
+    /// is interrupted if `SIGINT` is received meanwhile. It is up to
+    /// the caller to handle the signal once `call` returns, e.g. with
+    /// `Python::check_signals`.
+    pub(crate) fn call<T>(&self, call: impl FnOnce() -> T) -> T {
+        // A pending interruption must not be cancelled by a nested
+        // call, e.g. from a host function.
+        if !INTERRUPTED.load(Ordering::SeqCst) {
+            self.reset();
+        }
+
+        let _active_call = ActiveCall::enter(self.clone());
+
+        call()
+    }
+}
+
+/// Whether `SIGINT` has been received, and not yet handled by the
+/// watcher thread.
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// Identifies the calls in `State::calls`.
+static NEXT_CALL_ID: AtomicU64 = AtomicU64::new(0);
+
+struct State {
+    /// The calls that are running, from any thread.
+    calls: Vec<(u64, InterruptionHandle)>,
+
+    /// The watcher thread, once started.
+    watcher: Option<thread::Thread>,
+
+    /// The `SIGINT` handler replaced by `on_sigint` while calls are
+    /// running, if it has been replaced.
+    previous_handler: Option<signals::Handler>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    calls: Vec::new(),
+    watcher: None,
+    previous_handler: None,
+});
+
+/// Registers a running call, until it is dropped. The `SIGINT`
+/// handler is installed by the first call, and restored by the last
+/// one.
+struct ActiveCall {
+    id: u64,
+}
+
+impl ActiveCall {
+    fn enter(handle: InterruptionHandle) -> Self {
+        let id = NEXT_CALL_ID.fetch_add(1, Ordering::SeqCst);
+        let mut state = STATE.lock().unwrap_or_else(|error| error.into_inner());
+
+        if state.calls.is_empty() {
+            INTERRUPTED.store(false, Ordering::SeqCst);
+            state.previous_handler = signals::install();
+        }
+
+        state.calls.push((id, handle));
+
+        match &state.watcher {
+            Some(watcher) => watcher.unpark(),
+            None => {
+                state.watcher = thread::Builder::new()
+                    .name("wasmer-interruption-watcher".to_string())
+                    .spawn(watch)
+                    .ok()
+                    .map(|watcher| watcher.thread().clone());
+            }
+        }
+
+        Self { id }
+    }
+}
+
+impl Drop for ActiveCall {
+    fn drop(&mut self) {
+        let mut state = STATE.lock().unwrap_or_else(|error| error.into_inner());
+
+        state.calls.retain(|(id, _)| *id != self.id);
+
+        if state.calls.is_empty() {
+            if let Some(previous_handler) = state.previous_handler.take() {
+                signals::restore(previous_handler);
+            }
+        }
+    }
+}
+
+/// The watcher thread: while calls are running, it interrupts them
+/// once `SIGINT` has been received.
+fn watch() {
+    loop {
+        let idle = STATE.lock().map_or(true, |state| state.calls.is_empty());
+
+        if idle {
+            thread::park();
+
+            continue;
+        }
+
+        thread::sleep(WATCH_INTERVAL);
+
+        if INTERRUPTED.swap(false, Ordering::SeqCst) {
+            if let Ok(state) = STATE.lock() {
+                for (_, handle) in &state.calls {
+                    handle.interrupt();
+                }
+            }
+        }
+    }
+}
+
+/// Records a `SIGINT` for the watcher thread. It must only do what a
+/// signal handler can do.
+fn record_interruption() {
+    INTERRUPTED.store(true, Ordering::SeqCst);
+}
+
+#[cfg(unix)]
+mod signals {
+    use super::record_interruption;
+    use std::{
+        mem, ptr,
+        sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    };
+
+    pub(super) type Handler = libc::sigaction;
+
+    // The previous handler, read by `on_sigint` to forward the
+    // signal to it.
+    static PREVIOUS_ACTION: AtomicUsize = AtomicUsize::new(libc::SIG_DFL);
+    static PREVIOUS_TAKES_SIGINFO: AtomicBool = AtomicBool::new(false);
+
+    extern "C" fn on_sigint(
+        signal: libc::c_int,
+        info: *mut libc::siginfo_t,
+        context: *mut libc::c_void,
+    ) {
+        record_interruption();
+
+        let previous_action = PREVIOUS_ACTION.load(Ordering::SeqCst);
+
+        // SAFETY: `install` only replaces handlers that are
+        // functions, whose signature is given by `SA_SIGINFO`.
+        unsafe {
+            if PREVIOUS_TAKES_SIGINFO.load(Ordering::SeqCst) {
+                let previous: extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void) =
+                    mem::transmute(previous_action);
+                previous(signal, info, context);
+            } else {
+                let previous: extern "C" fn(libc::c_int) = mem::transmute(previous_action);
+                previous(signal);
+            }
+        }
+    }
+
+    /// Installs `on_sigint`, and returns the handler it replaces. The
+    /// handler is only replaced if it is a function, i.e. the Python
+    /// handler: an ignored signal, or one terminating the process,
+    /// must stay so.
+    pub(super) fn install() -> Option<Handler> {
+        unsafe {
+            let mut previous: libc::sigaction = mem::zeroed();
+
+            if libc::sigaction(libc::SIGINT, ptr::null(), &mut previous) != 0
+                || previous.sa_sigaction == libc::SIG_DFL
+                || previous.sa_sigaction == libc::SIG_IGN
+            {
+                return None;
+            }
+
+            PREVIOUS_ACTION.store(previous.sa_sigaction, Ordering::SeqCst);
+            PREVIOUS_TAKES_SIGINFO
+                .store(previous.sa_flags & libc::SA_SIGINFO != 0, Ordering::SeqCst);
+
+            let mut action: libc::sigaction = mem::zeroed();
+            action.sa_sigaction = on_sigint
+                as extern "C" fn(libc::c_int, *mut libc::siginfo_t, *mut libc::c_void)
+                as usize;
+            action.sa_flags = libc::SA_SIGINFO | (previous.sa_flags & libc::SA_ONSTACK);
+            libc::sigemptyset(&mut action.sa_mask);
+
+            if libc::sigaction(libc::SIGINT, &action, ptr::null_mut()) != 0 {
+                return None;
+            }
+
+            Some(previous)
+        }
+    }
+
+    pub(super) fn restore(previous: Handler) {
+        unsafe {
+            libc::sigaction(libc::SIGINT, &previous, ptr::null_mut());
+        }
+    }
+}
+
+#[cfg(windows)]
+mod signals {
+    use super::record_interruption;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    pub(super) type Handler = libc::sighandler_t;
+
+    // The previous handler, read by `on_sigint` to forward the
+    // signal to it.
+    static PREVIOUS_HANDLER: AtomicUsize = AtomicUsize::new(libc::SIG_DFL);
+
+    extern "C" fn on_sigint(signal: libc::c_int) {
+        record_interruption();
+
+        // SAFETY: `install` only replaces handlers that are
+        // functions.
+        let previous: extern "C" fn(libc::c_int) =
+            unsafe { std::mem::transmute(PREVIOUS_HANDLER.load(Ordering::SeqCst)) };
+        previous(signal);
+
+        // The handler is reset when a signal is delivered, and the
+        // Python handler reinstalls itself: reinstall this one over
+        // it.
+        unsafe {
+            libc::signal(libc::SIGINT, on_sigint as libc::sighandler_t);
+        }
+    }
+
+    /// See the Unix version.
+    pub(super) fn install() -> Option<Handler> {
+        unsafe {
+            let previous = libc::signal(libc::SIGINT, on_sigint as libc::sighandler_t);
+
+            if previous == libc::SIG_ERR {
+                return None;
+            }
+
+            if previous == libc::SIG_DFL || previous == libc::SIG_IGN {
+                libc::signal(libc::SIGINT, previous);
+
+                return None;
+            }
+
+            PREVIOUS_HANDLER.store(previous, Ordering::SeqCst);
+
+            Some(previous)
+        }
+    }
+
+    pub(super) fn restore(previous: Handler) {
+        unsafe {
+            libc::signal(libc::SIGINT, previous);
+        }
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+mod signals {
+    /// Calls can't be interrupted on this platform.
+    pub(super) type Handler = ();
+
+    pub(super) fn install() -> Option<Handler> {
+        None
+    }
+
+    pub(super) fn restore(_previous: Handler) {}
+}
@@ -1,4 +1,6 @@
+use crate::errors::to_py_err;
 use pyo3::{
+    exceptions::PyRuntimeError,
     prelude::*,
     types::{PyBytes, PyTuple},
     wrap_pymodule,
@@ -8,6 +10,8 @@ pub(crate) mod wasmer_inner {
     pub use wasmer;
     pub use wasmer_engines;
     pub use wasmer_types;
+    pub use wasmer_vfs;
+    pub use wasmer_vm;
     pub use wasmer_wasi;
 }
 
@@ -15,12 +19,15 @@ mod engines;
 mod errors;
 mod exports;
 mod externals;
+mod features;
 mod import_object;
 mod instance;
+mod interruption;
 mod memory;
 mod module;
 mod store;
 mod target;
+mod tunables;
 mod types;
 mod values;
 mod wasi;
@@ -89,22 +96,73 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     // Constants.
     module.add("__version__", env!("CARGO_PKG_VERSION"))?;
     module.add("__core_version__", env!("WASMER_VERSION"))?;
+    module.add(
+        "__core_version_info__",
+        (
+            env!("WASMER_VERSION_MAJOR")
+                .parse::<u32>()
+                .map_err(to_py_err::<PyRuntimeError, _>)?,
+            env!("WASMER_VERSION_MINOR")
+                .parse::<u32>()
+                .map_err(to_py_err::<PyRuntimeError, _>)?,
+            env!("WASMER_VERSION_PATCH")
+                .parse::<u32>()
+                .map_err(to_py_err::<PyRuntimeError, _>)?,
+        ),
+    )?;
+    module.add(
+        "__core_version_pre__",
+        Some(env!("WASMER_VERSION_PRE")).filter(|pre| !pre.is_empty()),
+    )?;
 
     // Functions.
 
     /// Translate WebAssembly text source to WebAssembly binary format.
     ///
+    /// The source is a string, or UTF-8 encoded bytes (any object
+    /// implementing the buffer protocol). Invalid UTF-8 raises a
+    /// `ValueError`, and an invalid source raises a `WatError`, whose
+    /// `line`, `column` and `text` attributes locate the error.
+    ///
     /// ## Example
     ///
     /// ```py
     /// from wasmer import wat2wasm
     ///
     /// assert wat2wasm('(module)') == b'\x00asm\x01\x00\x00\x00'
+    /// assert wat2wasm(b'(module)') == b'\x00asm\x01\x00\x00\x00'
+    /// ```
+    ///
+    /// ```py
+    /// from wasmer import wat2wasm, WatError
+    ///
+    /// try:
+    ///     wat2wasm('(module\n  (func (result i32) i32.const x))')
+    /// except WatError as error:
+    ///     assert (error.line, error.column) == (2, 32)
+    /// ```
+    ///
+    /// By default, the identifiers of the source (like `$sum`) are
+    /// preserved in the `name` custom section, so that `wasm2wat`
+    /// prints them back. With `generate_name_section=False`, the
+    /// `name` section is omitted, which makes the binary smaller.
+    ///
+    /// ```py
+    /// from wasmer import wat2wasm, wasm2wat
+    ///
+    /// source = '(module (func $answer (result i32) i32.const 42))'
+    ///
+    /// assert '$answer' in wasm2wat(wat2wasm(source))
+    /// assert '$answer' not in wasm2wat(wat2wasm(source, generate_name_section=False))
     /// ```
     #[pyfn(module)]
-    #[pyo3(text_signature = "(wat)")]
-    fn wat2wasm<'py>(py: Python<'py>, wat: String) -> PyResult<&'py PyBytes> {
-        wat::wat2wasm(py, wat)
+    #[pyo3(text_signature = "(wat, /, generate_name_section=True)")]
+    fn wat2wasm<'py>(
+        py: Python<'py>,
+        wat: &PyAny,
+        generate_name_section: Option<bool>,
+    ) -> PyResult<&'py PyBytes> {
+        wat::wat2wasm(py, wat, generate_name_section.unwrap_or(true))
     }
 
     /// Disassemble WebAssembly binary to WebAssembly text format.
@@ -116,10 +174,32 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     ///
     /// assert wasm2wat(b'\x00asm\x01\x00\x00\x00') == '(module)'
     /// ```
+    ///
+    /// With `canonical=True`, the output is normalized so that it
+    /// stays stable for a given module across versions of the
+    /// printer, e.g. for golden-file tests: comments (like the
+    /// `(;0;)` index annotations) are removed, whitespace is
+    /// collapsed, and lines are re-indented by 2 spaces per nesting
+    /// level.
+    ///
+    /// ```py
+    /// from wasmer import wat2wasm, wasm2wat
+    ///
+    /// wasm_bytes = wat2wasm('(module (func (export "f")))')
+    ///
+    /// assert wasm2wat(wasm_bytes, canonical=True) == \
+    ///     '(module\n  (type (func))\n  (func (type 0))\n  (export "f" (func 0)))'
+    /// ```
     #[pyfn(module)]
-    #[pyo3(text_signature = "(bytes)")]
-    fn wasm2wat(bytes: &PyBytes) -> PyResult<String> {
-        wat::wasm2wat(bytes)
+    #[pyo3(text_signature = "(bytes, /, canonical=False)")]
+    fn wasm2wat(bytes: &PyBytes, canonical: Option<bool>) -> PyResult<String> {
+        let wat = wat::wasm2wat(bytes)?;
+
+        Ok(if canonical.unwrap_or(false) {
+            wat::canonicalize(&wat)
+        } else {
+            wat
+        })
     }
 
     // Classes.
@@ -129,7 +209,9 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     module.add_class::<externals::Global>()?;
     module.add_class::<externals::Memory>()?;
     module.add_class::<externals::Table>()?;
+    module.add_class::<features::Features>()?;
     module.add_class::<import_object::ImportObject>()?;
+    module.add_class::<import_object::ImportObjectIterator>()?;
     module.add_class::<instance::Instance>()?;
     module.add_class::<memory::Buffer>()?;
     module.add_class::<memory::Float32Array>()?;
@@ -143,6 +225,8 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
     module.add_class::<memory::Uint64Array>()?;
     module.add_class::<memory::Uint8Array>()?;
     module.add_class::<module::Module>()?;
+    module.add_class::<module::ModuleExportsIterator>()?;
+    module.add_class::<module::ModuleImportsIterator>()?;
     module.add_class::<store::Store>()?;
     module.add_class::<types::ExportType>()?;
     module.add_class::<types::FunctionType>()?;
@@ -168,6 +252,10 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
         ))?,
     )?;
 
+    // Exceptions.
+    errors::register_exceptions(py, module)?;
+    wat::register_exceptions(py, module)?;
+
     // Modules.
     module.add_wrapped(wrap_pymodule!(engine))?;
     module.add_wrapped(wrap_pymodule!(target))?;
@@ -222,6 +310,43 @@ fn wasmer(py: Python, module: &PyModule) -> PyResult<()> {
 /// Engines are stored inside the `wasmer.Store`.
 #[pymodule]
 fn engine(_py: Python, module: &PyModule) -> PyResult<()> {
+    // Functions.
+
+    /// Load a serialized module (see `Module.serialize`) with a
+    /// headless engine, i.e. an engine without a compiler, in one
+    /// step. It is the intended workflow to deploy precompiled
+    /// modules.
+    ///
+    /// `path_or_bytes` is either the serialized module as `bytes`, or
+    /// a path (`str` or `os.PathLike`) to a file containing it.
+    ///
+    /// A `ValueError` is raised if the engine of the store is not
+    /// headless.
+    ///
+    /// ## Safety
+    ///
+    /// Like `Module.deserialize`, this function is inherently
+    /// **unsafe**: the serialized module is loaded into executable
+    /// memory as is, so it must come from a trusted source.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import engine, Store, Module, Instance
+    ///
+    /// serialized_module = Module(Store(), '(module)').serialize()
+    ///
+    /// store = Store(engine.Universal())
+    /// module = engine.load(store, serialized_module)
+    ///
+    /// assert isinstance(module, Module)
+    /// ```
+    #[pyfn(module)]
+    #[pyo3(text_signature = "(store, path_or_bytes)")]
+    fn load(store: &PyCell<store::Store>, path_or_bytes: &PyAny) -> PyResult<module::Module> {
+        engines::load(store, path_or_bytes)
+    }
+
     // Classes.
     module.add_class::<engines::Universal>()?;
     module.add_class::<engines::Dylib>()?;
@@ -347,6 +472,27 @@ fn wasi(py: Python, module: &PyModule) -> PyResult<()> {
         wasi::get_version(module, strict)
     }
 
+    /// Detect the version of WASI being used, like `get_version`, but
+    /// from the WebAssembly bytes directly: only the imports are
+    /// parsed, the module isn't compiled. `None` is returned if it
+    /// isn't a WASI module. A `ValueError` is raised if the bytes
+    /// are malformed.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    ///
+    /// wasm_bytes = open('tests/wasi.wasm', 'rb').read()
+    ///
+    /// assert wasi.detect_version(wasm_bytes, strict=True) == wasi.Version.SNAPSHOT1
+    /// ```
+    #[pyfn(module)]
+    #[pyo3(text_signature = "(bytes, strict)")]
+    fn detect_version(bytes: &PyBytes, strict: bool) -> PyResult<Option<wasi::Version>> {
+        wasi::detect_version(bytes.as_bytes(), strict)
+    }
+
     // Classes.
     module.add_class::<wasi::Environment>()?;
     module.add_class::<wasi::StateBuilder>()?;
@@ -1,10 +1,31 @@
-use crate::{errors::to_py_err, store::Store, types, wasmer_inner::wasmer};
+use crate::{
+    errors::{to_py_err, CompileError},
+    interruption::InterruptionHandle,
+    store::Store,
+    tunables, types,
+    wasmer_inner::{wasmer, wasmer_engines as engines, wasmer_types},
+};
 use pyo3::{
-    exceptions::{PyRuntimeError, PyTypeError},
+    buffer::PyBuffer,
+    class::{
+        basic::{CompareOp, PyObjectProtocol},
+        iter::PyIterProtocol,
+    },
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
     prelude::*,
-    types::{PyAny, PyBytes, PyList, PyString},
+    types::{PyAny, PyBytes, PyDict, PyList, PyString},
+    PyNativeType,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    convert::TryInto,
+    fs,
+    path::PathBuf,
+    process,
+    sync::{Arc, Mutex},
 };
-use std::convert::TryInto;
 
 /// A WebAssembly module contains stateless WebAssembly code that has
 /// already been compiled and can be instantiated multiple times.
@@ -34,16 +55,254 @@ use std::convert::TryInto;
 /// # Let's compile WebAssembly from WAT.
 /// module = Module(store, '(module)')
 /// ```
+///
+/// Modules are compared by content, not by identity: two modules
+/// compiled from the same bytes, by stores with the same
+/// configuration (engine, compiler, features and target), are equal
+/// and have the same hash. Deserialized modules are compared by their
+/// serialized artifact instead. Only a digest of the content is kept,
+/// so comparing modules is cheap.
+///
+/// ```py
+/// from wasmer import Store, Module
+///
+/// assert Module(Store(), '(module)') == Module(Store(), '(module)')
+/// assert Module(Store(), '(module)') != Module(Store(), '(module (func))')
+/// ```
 #[pyclass(unsendable)]
 #[pyo3(text_signature = "(store, bytes)")]
 pub struct Module {
     inner: wasmer::Module,
+    engine_name: String,
+
+    // A digest of the configuration of the store that has compiled
+    // the module, and of the content the module is compared by, see
+    // `Module::raw_new`.
+    digest: [u8; 32],
+
+    // The WebAssembly bytes, if the module has been compiled from
+    // them, see `Module.required_features`.
+    wasm_bytes: Option<Vec<u8>>,
+
+    // The Python store that has compiled the module, given to the
+    // externs exported by its instances, see `Memory.store`.
+    store: Py<Store>,
+
+    // The module moved into stores capping its memories, by number of
+    // pages, see `Module::limited`.
+    limited_modules: RefCell<Vec<(u32, wasmer::Module)>>,
+}
+
+/// What a module has been built from, see `Module::raw_new`.
+pub(crate) enum Source<'a> {
+    /// The WebAssembly bytes the module has been compiled from.
+    WasmBytes(Vec<u8>),
+
+    /// The (untagged) serialized artifact the module has been
+    /// deserialized from.
+    Artifact(&'a [u8]),
 }
 
 impl Module {
+    /// Builds a module compiled or deserialized by the store. Modules
+    /// are compared by content (see `Module`): their WebAssembly bytes
+    /// if they have been compiled from them, their serialized artifact
+    /// otherwise. The content is digested once, here, with the
+    /// configuration of the store.
+    pub(crate) fn raw_new(
+        store_cell: &PyCell<Store>,
+        inner: wasmer::Module,
+        source: Source,
+    ) -> Self {
+        let store = store_cell.borrow();
+
+        let mut hasher = Sha256::new();
+        hasher.update(store.config_digest());
+
+        let wasm_bytes = match source {
+            Source::WasmBytes(wasm_bytes) => {
+                hasher.update(b"wasm");
+                hasher.update(&wasm_bytes);
+
+                Some(wasm_bytes)
+            }
+            Source::Artifact(artifact) => {
+                hasher.update(b"artifact");
+                hasher.update(artifact);
+
+                None
+            }
+        };
+
+        let mut digest = [0; 32];
+        digest.copy_from_slice(&hasher.finalize());
+
+        Self {
+            inner,
+            engine_name: store.engine_name().clone(),
+            digest,
+            wasm_bytes,
+            store: store_cell.into(),
+            limited_modules: RefCell::default(),
+        }
+    }
+
+    /// Returns the module moved into a store sharing its engine, but
+    /// whose tunables cap the memories created by the module to
+    /// `max_memory_pages` pages, see `Instance`. Moving the module
+    /// requires to serialize and to deserialize it, so it is done
+    /// once per cap.
+    pub(crate) fn limited(&self, max_memory_pages: u32) -> PyResult<wasmer::Module> {
+        if max_memory_pages > wasmer::WASM_MAX_PAGES {
+            return Err(to_py_err::<PyValueError, _>(format!(
+                "`max_memory_pages` must be at most {}, got {}",
+                wasmer::WASM_MAX_PAGES,
+                max_memory_pages
+            )));
+        }
+
+        let mut limited_modules = self.limited_modules.borrow_mut();
+
+        if let Some((_, limited_module)) = limited_modules
+            .iter()
+            .find(|(limit, _)| *limit == max_memory_pages)
+        {
+            return Ok(limited_module.clone());
+        }
+
+        let store = tunables::limited_store(self.inner.store(), wasmer::Pages(max_memory_pages));
+        let artifact = self
+            .inner
+            .serialize()
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        // SAFETY: The artifact has just been serialized by the same
+        // engine.
+        let limited_module = unsafe { wasmer::Module::deserialize(&store, &artifact) }
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+        limited_modules.push((max_memory_pages, limited_module.clone()));
+
+        Ok(limited_module)
+    }
+
+    /// Compile the module, or load it from the store's cache if the
+    /// store has one. A freshly compiled module is written in the
+    /// cache. Failing to read or write the cache isn't an error: the
+    /// cache is only an optimisation.
+    fn compile_with_cache(
+        store: &Store,
+        bytes: &[u8],
+    ) -> Result<wasmer::Module, wasmer::CompileError> {
+        let cache_entry = match store.cache_entry(bytes) {
+            Some(cache_entry) => cache_entry,
+            None => return wasmer::Module::new(store.inner(), bytes),
+        };
+
+        if let Ok(content) = fs::read(&cache_entry.path) {
+            if let Some(artifact) = cache_entry.artifact(&content) {
+                // SAFETY: The artifact has been written by the same
+                // engine, with the same configuration, for the same
+                // bytes, see `CacheEntry`; the cache directory is
+                // trusted, see `Store`.
+                if let Ok(module) = unsafe { wasmer::Module::deserialize(store.inner(), artifact) }
+                {
+                    return Ok(module);
+                }
+            }
+        }
+
+        let module = wasmer::Module::new(store.inner(), bytes)?;
+
+        if let Some(cache_dir) = cache_entry.path.parent() {
+            // Write to a temporary file first, so that a concurrent
+            // reader never sees a partially written artifact.
+            let temporary_path = cache_entry
+                .path
+                .with_extension(format!("{}.tmp", process::id()));
+
+            let written = fs::create_dir_all(cache_dir).is_ok()
+                && module
+                    .serialize()
+                    .map(|artifact| [cache_entry.header(), artifact].concat())
+                    .is_ok_and(|content| fs::write(&temporary_path, content).is_ok())
+                && fs::rename(&temporary_path, &cache_entry.path).is_ok();
+
+            if !written {
+                let _ = fs::remove_file(&temporary_path);
+            }
+        }
+
+        Ok(module)
+    }
+
+    pub(crate) fn store(&self) -> &Py<Store> {
+        &self.store
+    }
+
     pub(crate) fn inner(&self) -> &wasmer::Module {
         &self.inner
     }
+
+    /// Reads the bytes of a module as if there were real bytes or a
+    /// WAT string.
+    fn read_bytes(bytes: &PyAny) -> PyResult<Cow<'_, [u8]>> {
+        Ok(if let Ok(bytes) = bytes.downcast::<PyBytes>() {
+            Cow::Borrowed(bytes.as_bytes())
+        } else if let Ok(string) = bytes.downcast::<PyString>() {
+            Cow::Borrowed(string.to_str()?.as_bytes())
+        } else if let Ok(buffer) = PyBuffer::<u8>::get(bytes) {
+            Cow::Owned(buffer.to_vec(bytes.py())?)
+        } else {
+            return Err(to_py_err::<PyTypeError, _>(
+                "`Module` accepts Wasm bytes (any object implementing the buffer protocol) or a WAT string",
+            ));
+        })
+    }
+
+    /// Rejects WebAssembly components with a dedicated error: a
+    /// component starts with the same `\0asm` magic as a core
+    /// module, but its preamble has a layer of 1 (bytes 6 and 7)
+    /// instead of 0.
+    fn check_is_not_component(bytes: &[u8]) -> PyResult<()> {
+        if bytes.len() >= 8 && bytes[0..4] == *b"\0asm" && bytes[6..8] == [0x01, 0x00] {
+            return Err(to_py_err::<PyValueError, _>(
+                "This is a WebAssembly component, not a core module; component support is not available.",
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the exports whose type satisfies `predicate`.
+    fn filtered_exports(
+        &self,
+        predicate: fn(&wasmer::ExternType) -> bool,
+    ) -> PyResult<Vec<types::ExportType>> {
+        self.visible_exports()
+            .filter(|export| predicate(export.ty()))
+            .map(TryInto::try_into)
+            .collect()
+    }
+
+    /// Returns the exports of the module, without the export holding
+    /// the interruption flag, see `InterruptionHandle::is_reserved`.
+    fn visible_exports(&self) -> impl Iterator<Item = wasmer::ExportType> + '_ {
+        self.inner
+            .exports()
+            .filter(|export| !InterruptionHandle::is_reserved(export.name()))
+    }
+
+    /// Returns the imports whose type satisfies `predicate`.
+    fn filtered_imports(
+        &self,
+        predicate: fn(&wasmer::ExternType) -> bool,
+    ) -> PyResult<Vec<types::ImportType>> {
+        self.inner
+            .imports()
+            .filter(|import| predicate(import.ty()))
+            .map(TryInto::try_into)
+            .collect()
+    }
 }
 
 #[pymethods]
@@ -71,24 +330,211 @@ impl Module {
         }
     }
 
+    /// Validates a new WebAssembly Module like `Module.validate`, but
+    /// explains why the validation has failed: it returns `None` if
+    /// the module is valid, and the validation error message
+    /// otherwise. It never raises.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    ///
+    /// assert Module.validate_detailed(store, wasm_bytes) == None
+    /// assert Module.validate_detailed(store, b'\x00asm') != None
+    /// ```
+    #[pyo3(text_signature = "(store, bytes)")]
+    #[staticmethod]
+    fn validate_detailed(store: &Store, bytes: &PyAny) -> Option<String> {
+        match bytes.downcast::<PyBytes>() {
+            Ok(bytes) => wasmer::Module::validate(store.inner(), bytes.as_bytes())
+                .err()
+                .map(|error| error.to_string()),
+            _ => Some(format!(
+                "The module must be given as `bytes`, got `{}`",
+                bytes.get_type().name().unwrap_or("?")
+            )),
+        }
+    }
+
+    /// Checks whether the given data looks like a WebAssembly
+    /// binary, i.e. it starts with the `b"\0asm"` magic number
+    /// followed by the supported binary format version.
+    ///
+    /// This function never raises: anything that isn't `bytes`
+    /// returns `False`. The module isn't validated nor compiled, see
+    /// `Module.validate` for that.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Module
+    ///
+    /// assert Module.looks_like_wasm(b'\x00asm\x01\x00\x00\x00')
+    /// assert not Module.looks_like_wasm(b'(module)')
+    /// assert not Module.looks_like_wasm('(module)')
+    /// ```
+    #[pyo3(text_signature = "(data)")]
+    #[staticmethod]
+    fn looks_like_wasm(data: &PyAny) -> bool {
+        match data.downcast::<PyBytes>() {
+            Ok(bytes) => bytes.as_bytes().starts_with(b"\0asm\x01\0\0\0"),
+            _ => false,
+        }
+    }
+
+    /// Checks whether the given text is valid WebAssembly text
+    /// format, by trying to parse it.
+    ///
+    /// This function never raises: anything that isn't a `str`
+    /// returns `False`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Module
+    ///
+    /// assert Module.looks_like_wat('(module)')
+    /// assert not Module.looks_like_wat('(modul')
+    /// assert not Module.looks_like_wat(b'\x00asm\x01\x00\x00\x00')
+    /// ```
+    #[pyo3(text_signature = "(text)")]
+    #[staticmethod]
+    fn looks_like_wat(text: &PyAny) -> bool {
+        match text.downcast::<PyString>() {
+            Ok(string) => match string.to_str() {
+                Ok(string) => wat::parse_str(string).is_ok(),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Reads the name of a module from its WebAssembly bytes, i.e.
+    /// from the `name` custom section, without compiling the module.
+    /// See `Module.name`.
+    ///
+    /// This function never raises: malformed bytes, or anything that
+    /// isn't `bytes`, return `None`, like a module without a name.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Module, wat2wasm
+    ///
+    /// assert Module.read_name(wat2wasm('(module $moduleName)')) == 'moduleName'
+    /// assert Module.read_name(wat2wasm('(module)')) == None
+    /// assert Module.read_name(b'garbage') == None
+    /// ```
+    #[pyo3(text_signature = "(bytes)")]
+    #[staticmethod]
+    fn read_name(bytes: &PyAny) -> Option<String> {
+        let bytes = bytes.downcast::<PyBytes>().ok()?.as_bytes();
+
+        for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+            if let wasmparser::Payload::CustomSection {
+                name: "name",
+                data,
+                data_offset,
+                ..
+            } = payload.ok()?
+            {
+                for name in wasmparser::NameSectionReader::new(data, data_offset).ok()? {
+                    if let wasmparser::Name::Module(module_name) = name.ok()? {
+                        return module_name.get_name().ok().map(ToString::to_string);
+                    }
+                }
+
+                return None;
+            }
+        }
+
+        None
+    }
+
     #[new]
-    fn new(store: &Store, bytes: &PyAny) -> PyResult<Self> {
-        let store = store.inner();
+    pub(crate) fn new(store_cell: &PyCell<Store>, bytes: &PyAny) -> PyResult<Self> {
+        let store = store_cell.borrow();
+        let store: &Store = &store;
+        let bytes = Self::read_bytes(bytes)?;
+        Self::check_is_not_component(&bytes)?;
+        store.check_can_compile()?;
 
-        // Read the bytes as if there were real bytes or a WAT string.
-        let module = if let Ok(bytes) = bytes.downcast::<PyBytes>() {
-            wasmer::Module::new(store, bytes.as_bytes())
-        } else if let Ok(string) = bytes.downcast::<PyString>() {
-            wasmer::Module::new(store, string.to_str()?.as_bytes())
-        } else {
-            return Err(to_py_err::<PyTypeError, _>(
-                "`Module` accepts Wasm bytes or a WAT string",
-            ));
+        let wasm_bytes = wat::parse_bytes(&bytes).map_err(to_py_err::<CompileError, _>)?;
+
+        let module =
+            Module::compile_with_cache(store, &wasm_bytes).map_err(to_py_err::<CompileError, _>)?;
+
+        Ok(Module::raw_new(
+            store_cell,
+            module,
+            Source::WasmBytes(wasm_bytes.into_owned()),
+        ))
+    }
+
+    /// Compiles a module like `Module(store, bytes)`, but on a worker
+    /// thread, without holding the GIL, so that compiling a large
+    /// module doesn't freeze the interpreter.
+    ///
+    /// It must be called while an event loop is running, e.g. from a
+    /// coroutine, otherwise `RuntimeError` is raised. It returns an
+    /// `asyncio.Future` of the running loop, which resolves with the
+    /// `Module`, or with the `CompileError`. The compilation runs in
+    /// the default executor of the loop (see
+    /// `loop.run_in_executor`), and the `Module` itself is created on
+    /// the event loop thread.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// import asyncio
+    /// from wasmer import Store, Module
+    ///
+    /// async def main():
+    ///     module = await Module.compile_async(Store(), '(module)')
+    ///
+    ///     assert isinstance(module, Module)
+    ///
+    /// asyncio.run(main())
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(store, bytes)")]
+    fn compile_async(py: Python, store: Py<Store>, bytes: &PyAny) -> PyResult<PyObject> {
+        let bytes = Self::read_bytes(bytes)?;
+        Self::check_is_not_component(&bytes)?;
+        store.borrow(py).check_can_compile()?;
+
+        let wasm_bytes = wat::parse_bytes(&bytes)
+            .map_err(to_py_err::<CompileError, _>)?
+            .into_owned();
+
+        let event_loop = py.import("asyncio")?.call_method0("get_running_loop")?;
+        let future = event_loop.call_method0("create_future")?.to_object(py);
+
+        // The module compiled by the worker thread, turned into a
+        // `Module` on the event loop thread, as `Module` is
+        // unsendable.
+        let compiled_module = Arc::new(Mutex::new(None));
+
+        let compile = CompileTask {
+            store: store.clone_ref(py),
+            wasm_bytes: wasm_bytes.clone(),
+            compiled_module: compiled_module.clone(),
+        };
+        let done = CompileDone {
+            future: future.clone_ref(py),
+            store,
+            wasm_bytes,
+            compiled_module,
         };
 
-        Ok(Module {
-            inner: module.map_err(to_py_err::<PyRuntimeError, _>)?,
-        })
+        event_loop
+            .call_method1("run_in_executor", (py.None(), Py::new(py, compile)?))?
+            .call_method1("add_done_callback", (Py::new(py, done)?,))?;
+
+        Ok(future)
     }
 
     /// Get or set the current name of the module.
@@ -115,15 +561,21 @@ impl Module {
     /// module = Module(store, '(module $moduleName)')
     /// module.name = 'hello'
     /// assert module.name == 'hello'
+    ///
+    /// # Remove the module's name.
+    /// module.name = None
+    /// assert module.name == None
     /// ```
     #[getter]
     fn name(&self) -> Option<&str> {
-        self.inner.name()
+        self.inner.name().filter(|name| !name.is_empty())
     }
 
     #[setter(name)]
-    fn set_name(&mut self, name: &str) -> PyResult<()> {
-        self.inner.set_name(name);
+    fn set_name(&mut self, name: Option<&str>) -> PyResult<()> {
+        // Wasmer doesn't provide a way to unset the name, so an empty
+        // name is considered as no name.
+        self.inner.set_name(name.unwrap_or(""));
 
         Ok(())
     }
@@ -139,7 +591,30 @@ impl Module {
     /// See the `ExportType` class to learn more.
     #[getter]
     fn exports(&self) -> PyResult<Vec<types::ExportType>> {
-        self.inner.exports().map(TryInto::try_into).collect()
+        self.visible_exports().map(TryInto::try_into).collect()
+    }
+
+    /// Returns an iterator over the exports of this module, yielding
+    /// `ExportType` objects one at a time, in the same order as
+    /// `Module.exports`. Contrary to `Module.exports`, no list is
+    /// built: each `ExportType` is created when it is reached.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(Store(), '(module (func (export "f")) (memory (export "m") 1))')
+    ///
+    /// assert next(module.iter_exports()).name == 'f'
+    /// assert [export.name for export in module.iter_exports()] == ['f', 'm']
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn iter_exports(&self) -> ModuleExportsIterator {
+        ModuleExportsIterator {
+            module: self.inner.clone(),
+            index: 0,
+        }
     }
 
     /// Returns a list of `ImportType` objects, which represents all
@@ -156,6 +631,231 @@ impl Module {
         self.inner.imports().map(TryInto::try_into).collect()
     }
 
+    /// Returns an iterator over the imports of this module, yielding
+    /// `ImportType` objects one at a time, in the same order as
+    /// `Module.imports`. See `Module.iter_exports`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(Store(), '(module (import "ns" "f" (func)))')
+    ///
+    /// assert next(module.iter_imports()).name == 'f'
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn iter_imports(&self) -> ModuleImportsIterator {
+        ModuleImportsIterator {
+            module: self.inner.clone(),
+            index: 0,
+        }
+    }
+
+    /// Returns the exports of the module that are functions, as a list
+    /// of `ExportType` objects. See `Module.exports`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module, FunctionType
+    ///
+    /// module = Module(Store(), '(module (func (export "f")) (memory (export "m") 1))')
+    ///
+    /// assert [export.name for export in module.exported_functions] == ['f']
+    /// assert isinstance(module.exported_functions[0].type, FunctionType)
+    /// ```
+    #[getter]
+    fn exported_functions(&self) -> PyResult<Vec<types::ExportType>> {
+        self.filtered_exports(|ty| matches!(ty, wasmer::ExternType::Function(_)))
+    }
+
+    /// Returns the exports of the module that are memories, as a list
+    /// of `ExportType` objects. See `Module.exports`.
+    #[getter]
+    fn exported_memories(&self) -> PyResult<Vec<types::ExportType>> {
+        self.filtered_exports(|ty| matches!(ty, wasmer::ExternType::Memory(_)))
+    }
+
+    /// Returns the exports of the module that are globals, as a list
+    /// of `ExportType` objects. See `Module.exports`.
+    #[getter]
+    fn exported_globals(&self) -> PyResult<Vec<types::ExportType>> {
+        self.filtered_exports(|ty| matches!(ty, wasmer::ExternType::Global(_)))
+    }
+
+    /// Returns the exports of the module that are tables, as a list
+    /// of `ExportType` objects. See `Module.exports`.
+    #[getter]
+    fn exported_tables(&self) -> PyResult<Vec<types::ExportType>> {
+        self.filtered_exports(|ty| matches!(ty, wasmer::ExternType::Table(_)))
+    }
+
+    /// Returns the imports of the module that are functions, as a list
+    /// of `ImportType` objects. See `Module.imports`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(Store(), '(module (import "env" "f" (func)) (import "env" "m" (memory 1)))')
+    ///
+    /// assert [import_.name for import_ in module.imported_functions] == ['f']
+    /// ```
+    #[getter]
+    fn imported_functions(&self) -> PyResult<Vec<types::ImportType>> {
+        self.filtered_imports(|ty| matches!(ty, wasmer::ExternType::Function(_)))
+    }
+
+    /// Returns the imports of the module that are memories, as a list
+    /// of `ImportType` objects. See `Module.imports`.
+    #[getter]
+    fn imported_memories(&self) -> PyResult<Vec<types::ImportType>> {
+        self.filtered_imports(|ty| matches!(ty, wasmer::ExternType::Memory(_)))
+    }
+
+    /// Returns the imports of the module that are globals, as a list
+    /// of `ImportType` objects. See `Module.imports`.
+    #[getter]
+    fn imported_globals(&self) -> PyResult<Vec<types::ImportType>> {
+        self.filtered_imports(|ty| matches!(ty, wasmer::ExternType::Global(_)))
+    }
+
+    /// Returns the imports of the module that are tables, as a list
+    /// of `ImportType` objects. See `Module.imports`.
+    #[getter]
+    fn imported_tables(&self) -> PyResult<Vec<types::ImportType>> {
+        self.filtered_imports(|ty| matches!(ty, wasmer::ExternType::Table(_)))
+    }
+
+    /// The number of imports of the module. It is cheaper than
+    /// `len(module.imports)`, as no `ImportType` object is built.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(Store(), '(module (import "env" "f" (func)))')
+    ///
+    /// assert module.import_count == 1
+    /// ```
+    #[getter]
+    fn import_count(&self) -> usize {
+        self.inner.imports().len()
+    }
+
+    /// The number of exports of the module. It is cheaper than
+    /// `len(module.exports)`, as no `ExportType` object is built.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(Store(), '(module (func (export "f")))')
+    ///
+    /// assert module.export_count == 1
+    /// ```
+    #[getter]
+    fn export_count(&self) -> usize {
+        self.visible_exports().count()
+    }
+
+    /// Returns a dictionary indicating, for each WebAssembly proposal,
+    /// whether the module requires it, i.e. whether its bytecode
+    /// uses the proposal. It is determined by scanning the module's
+    /// bytes: a proposal is required if the module doesn't validate
+    /// once the proposal is disabled.
+    ///
+    /// The keys are `bulk_memory`, `exceptions`, `memory64`,
+    /// `module_linking`, `multi_memory`, `multi_value`,
+    /// `reference_types`, `simd`, `tail_call` and `threads`.
+    ///
+    /// It is only available for modules compiled from bytes, not for
+    /// deserialized modules, in which case a `RuntimeError` is
+    /// raised.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(Store(), '(module (func (result i32 i32) i32.const 1 i32.const 2))')
+    /// features = module.required_features()
+    ///
+    /// assert features['multi_value'] == True
+    /// assert features['simd'] == False
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn required_features<'py>(&self, py: Python<'py>) -> PyResult<&'py PyDict> {
+        let wasm_bytes = self.wasm_bytes.as_ref().ok_or_else(|| {
+            to_py_err::<PyRuntimeError, _>(
+                "The required features are only available for modules compiled from bytes",
+            )
+        })?;
+
+        let all_features = wasmparser::WasmFeatures {
+            reference_types: true,
+            multi_value: true,
+            bulk_memory: true,
+            module_linking: true,
+            simd: true,
+            threads: true,
+            tail_call: true,
+            multi_memory: true,
+            exceptions: true,
+            memory64: true,
+            ..Default::default()
+        };
+        let requires = |disable: fn(&mut wasmparser::WasmFeatures)| {
+            let mut features = all_features;
+            disable(&mut features);
+
+            let mut validator = wasmparser::Validator::new();
+            validator.wasm_features(features);
+
+            validator.validate_all(wasm_bytes).is_err()
+        };
+
+        let required_features = PyDict::new(py);
+        required_features.set_item("bulk_memory", requires(|f| f.bulk_memory = false))?;
+        required_features.set_item("exceptions", requires(|f| f.exceptions = false))?;
+        required_features.set_item("memory64", requires(|f| f.memory64 = false))?;
+        required_features.set_item("module_linking", requires(|f| f.module_linking = false))?;
+        required_features.set_item("multi_memory", requires(|f| f.multi_memory = false))?;
+        required_features.set_item("multi_value", requires(|f| f.multi_value = false))?;
+        required_features.set_item("reference_types", requires(|f| f.reference_types = false))?;
+        required_features.set_item("simd", requires(|f| f.simd = false))?;
+        required_features.set_item("tail_call", requires(|f| f.tail_call = false))?;
+        required_features.set_item("threads", requires(|f| f.threads = false))?;
+
+        Ok(required_features)
+    }
+
+    /// Checks whether the module has a start function, i.e. a
+    /// `(start $f)` section. The start function is automatically run
+    /// when the module is instantiated.
+    ///
+    /// It differs from a `_start` exported function, as used by WASI
+    /// command modules.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// store = Store()
+    ///
+    /// assert Module(store, '(module (func $f) (start $f))').has_start_function
+    /// assert not Module(store, '(module)').has_start_function
+    /// ```
+    #[getter]
+    fn has_start_function(&self) -> bool {
+        self.inner.info().start_function.is_some()
+    }
+
     /// Get the custom sections of the module given a `name`.
     ///
     /// ## Important
@@ -189,6 +889,49 @@ impl Module {
         )
     }
 
+    /// Returns a new module, compiled from the same WebAssembly bytes
+    /// without their custom sections (like `name` or the DWARF debug
+    /// sections), except the ones whose name is in `keep`. It is
+    /// useful to shrink a module, and to not leak build information,
+    /// before distributing it.
+    ///
+    /// A `RuntimeError` is raised if the module hasn't been compiled
+    /// from bytes, e.g. if it has been deserialized.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Module
+    ///
+    /// module = Module(Store(), open('tests/custom_sections.wasm', 'rb').read())
+    /// stripped_module = module.strip(keep=['hello'])
+    ///
+    /// assert stripped_module.custom_sections('easter_egg') == []
+    /// assert stripped_module.custom_sections('hello') == [b'World!']
+    /// ```
+    #[pyo3(text_signature = "($self, /, keep=None)")]
+    #[args(keep = "None")]
+    fn strip(&self, py: Python, keep: Option<Vec<String>>) -> PyResult<Self> {
+        let wasm_bytes = self.wasm_bytes.as_ref().ok_or_else(|| {
+            to_py_err::<PyRuntimeError, _>("Only modules compiled from bytes can be stripped")
+        })?;
+        let keep = keep.unwrap_or_default();
+
+        let stripped_bytes = crate::wat::strip_custom_sections(wasm_bytes, |name| {
+            keep.iter().any(|kept| kept == name)
+        })
+        .map_err(to_py_err::<CompileError, _>)?;
+
+        let module = wasmer::Module::new(self.inner.store(), &stripped_bytes)
+            .map_err(to_py_err::<CompileError, _>)?;
+
+        Ok(Module::raw_new(
+            self.store.as_ref(py),
+            module,
+            Source::WasmBytes(stripped_bytes),
+        ))
+    }
+
     /// Serializes a module into a binary representation that the
     /// `Engine` can later process via `Module.deserialize`.
     ///
@@ -205,15 +948,90 @@ impl Module {
     /// ```
     #[pyo3(text_signature = "($self)")]
     fn serialize<'p>(&self, py: Python<'p>) -> PyResult<&'p PyBytes> {
+        let artifact = self
+            .inner
+            .serialize()
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
         Ok(PyBytes::new(
             py,
-            self.inner
-                .serialize()
-                .map_err(to_py_err::<PyRuntimeError, _>)?
-                .as_slice(),
+            &[
+                SERIALIZED_MAGIC,
+                SERIALIZED_VERSION.as_bytes(),
+                b"\0",
+                &artifact,
+            ]
+            .concat(),
         ))
     }
 
+    /// Reads the version of Wasmer that has serialized a module (see
+    /// `Module.serialize`), from the serialized bytes. `None` is
+    /// returned if the bytes don't carry a version, e.g. if they have
+    /// been serialized by an older version of this package.
+    ///
+    /// `Module.deserialize` refuses to load a module serialized by
+    /// another version of Wasmer, as the serialized format is
+    /// version-sensitive.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// import wasmer
+    /// from wasmer import Store, Module
+    ///
+    /// serialized_module = Module(Store(), '(module)').serialize()
+    ///
+    /// assert Module.serialized_format_version(serialized_module) == wasmer.__core_version__
+    /// assert Module.serialized_format_version(b'foo') == None
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(bytes)")]
+    fn serialized_format_version(bytes: &PyBytes) -> Option<&str> {
+        split_serialized(bytes.as_bytes()).0
+    }
+
+    /// Writes the shared object (`.so`, `.dylib` or `.dll` depending
+    /// on the target) generated for this module to `path`. It allows
+    /// to keep a precompiled native artifact, e.g. for deployment.
+    ///
+    /// The module must have been compiled with a store using the
+    /// `engine.Dylib` engine, otherwise a `RuntimeError` is raised.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import engine, Store, Module
+    /// from wasmer_compiler_cranelift import Compiler
+    /// import os
+    /// import tempfile
+    ///
+    /// store = Store(engine.Dylib(Compiler))
+    /// module = Module(store, '(module)')
+    ///
+    /// with tempfile.TemporaryDirectory() as directory:
+    ///     path = os.path.join(directory, 'module.so')
+    ///     module.write_dylib(path)
+    ///
+    ///     assert os.path.exists(path)
+    /// ```
+    #[pyo3(text_signature = "($self, path)")]
+    fn write_dylib(&self, path: PathBuf) -> PyResult<()> {
+        if self.engine_name != engines::Dylib::name() {
+            return Err(to_py_err::<PyRuntimeError, _>(format!(
+                "The module has been compiled with the `{}` engine, not the `{}` engine",
+                self.engine_name,
+                engines::Dylib::name()
+            )));
+        }
+
+        // With the Dylib engine, the serialized module is the shared
+        // object itself.
+        self.inner
+            .serialize_to_file(path)
+            .map_err(to_py_err::<PyRuntimeError, _>)
+    }
+
     /// Deserializes a serialized module binary into a `Module`.
     ///
     /// **Note**: the module has to be serialized before with the
@@ -230,6 +1048,17 @@ impl Module {
     ///
     /// And as such, the `deserialize` method is unsafe.
     ///
+    /// A `ValueError` is raised if the module has been serialized by
+    /// another version of Wasmer, see
+    /// `Module.serialized_format_version`.
+    ///
+    /// With `trusted=False`, the bytes are structurally checked
+    /// before being loaded, e.g. the artifact header and format
+    /// version must match the store's engine, and a `ValueError` is
+    /// raised for malformed bytes. This catches corrupted or
+    /// mismatched artifacts, but it **cannot** validate arbitrary
+    /// machine code: only deserialize bytes from a trusted source.
+    ///
     /// ## Example
     ///
     /// ```py
@@ -253,12 +1082,260 @@ impl Module {
     ///
     /// assert isinstance(module, Module)
     /// ```
-    #[pyo3(text_signature = "($self, bytes)")]
+    #[pyo3(text_signature = "($self, bytes, /, trusted=True)")]
     #[staticmethod]
-    fn deserialize(store: &Store, bytes: &PyBytes) -> PyResult<Self> {
-        let module = unsafe { wasmer::Module::deserialize(store.inner(), bytes.as_bytes()) }
+    #[args(trusted = "true")]
+    fn deserialize(store_cell: &PyCell<Store>, bytes: &PyBytes, trusted: bool) -> PyResult<Self> {
+        let store = store_cell.borrow();
+        let bytes = untag_serialized(bytes.as_bytes())?;
+
+        if !trusted {
+            let engine_name = store.engine_name();
+            let is_deserializable = if engine_name == engines::Universal::name() {
+                wasmer::UniversalArtifact::is_deserializable(bytes)
+            } else if engine_name == engines::Dylib::name() {
+                wasmer::DylibArtifact::is_deserializable(bytes)
+            } else {
+                false
+            };
+
+            if !is_deserializable {
+                return Err(to_py_err::<PyValueError, _>(format!(
+                    "The bytes are not a valid serialized module for the `{}` engine",
+                    engine_name
+                )));
+            }
+        }
+
+        let module = unsafe { wasmer::Module::deserialize(store.inner(), bytes) }
             .map_err(to_py_err::<PyRuntimeError, _>)?;
 
-        Ok(Module { inner: module })
+        Ok(Module::raw_new(store_cell, module, Source::Artifact(bytes)))
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for Module {
+    fn __richcmp__(&self, other: PyRef<Module>, op: CompareOp) -> PyResult<PyObject> {
+        let py = other.py();
+
+        let equal = self.digest == other.digest;
+
+        Ok(match op {
+            CompareOp::Eq => equal.to_object(py),
+            CompareOp::Ne => (!equal).to_object(py),
+            _ => py.NotImplemented(),
+        })
+    }
+
+    fn __hash__(&self) -> u64 {
+        let mut prefix = [0; 8];
+        prefix.copy_from_slice(&self.digest[..8]);
+
+        u64::from_le_bytes(prefix)
+    }
+}
+
+/// The prefix of the modules serialized by `Module.serialize`, followed
+/// by the version of Wasmer, a nul byte, and the artifact itself.
+const SERIALIZED_MAGIC: &[u8] = b"\0wasmer-python-module\0";
+
+/// The version of Wasmer serializing the modules.
+const SERIALIZED_VERSION: &str = env!("WASMER_VERSION");
+
+/// Splits a serialized module into the version of Wasmer that has
+/// serialized it, if any, and the artifact.
+fn split_serialized(bytes: &[u8]) -> (Option<&str>, &[u8]) {
+    bytes
+        .strip_prefix(SERIALIZED_MAGIC)
+        .and_then(|tagged| {
+            let end = tagged.iter().position(|byte| *byte == 0)?;
+            let version = std::str::from_utf8(&tagged[..end]).ok()?;
+
+            Some((Some(version), &tagged[end + 1..]))
+        })
+        .unwrap_or((None, bytes))
+}
+
+/// Returns the artifact of a serialized module, checking it has been
+/// serialized by the current version of Wasmer. Untagged bytes are
+/// returned as is.
+pub(crate) fn untag_serialized(bytes: &[u8]) -> PyResult<&[u8]> {
+    match split_serialized(bytes) {
+        (Some(version), _) if version != SERIALIZED_VERSION => {
+            Err(to_py_err::<PyValueError, _>(format!(
+                "The module has been serialized with wasmer {}, cannot load it under wasmer {}",
+                version, SERIALIZED_VERSION
+            )))
+        }
+        (_, artifact) => Ok(artifact),
+    }
+}
+
+/// The callable compiling a module on a worker thread, see
+/// `Module.compile_async`.
+#[pyclass]
+struct CompileTask {
+    store: Py<Store>,
+    wasm_bytes: Vec<u8>,
+    compiled_module: Arc<Mutex<Option<wasmer::Module>>>,
+}
+
+#[pymethods]
+impl CompileTask {
+    #[call]
+    fn __call__(&self, py: Python) -> PyResult<()> {
+        let store = self.store.borrow(py);
+        let store: &Store = &store;
+        let wasm_bytes = &self.wasm_bytes;
+
+        let module = py
+            .allow_threads(|| Module::compile_with_cache(store, wasm_bytes))
+            .map_err(to_py_err::<CompileError, _>)?;
+        *self.compiled_module.lock().unwrap() = Some(module);
+
+        Ok(())
+    }
+}
+
+/// The callable resolving the future of `Module.compile_async` once
+/// the `CompileTask` is done, on the event loop thread.
+#[pyclass]
+struct CompileDone {
+    future: PyObject,
+    store: Py<Store>,
+    wasm_bytes: Vec<u8>,
+    compiled_module: Arc<Mutex<Option<wasmer::Module>>>,
+}
+
+#[pymethods]
+impl CompileDone {
+    #[call]
+    fn __call__(&self, py: Python, task: &PyAny) -> PyResult<()> {
+        let future = self.future.as_ref(py);
+
+        if future.call_method0("cancelled")?.is_true()? {
+            return Ok(());
+        }
+
+        match task.call_method0("result") {
+            Ok(_) => {
+                let module = self.compiled_module.lock().unwrap().take().ok_or_else(|| {
+                    to_py_err::<PyRuntimeError, _>("The module has not been compiled")
+                })?;
+                let module = Module::raw_new(
+                    self.store.as_ref(py),
+                    module,
+                    Source::WasmBytes(self.wasm_bytes.clone()),
+                );
+
+                future.call_method1("set_result", (Py::new(py, module)?,))?;
+            }
+            Err(error) => {
+                future.call_method1("set_exception", (error.to_object(py),))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Lazy iterator over the exports of a `Module`, see
+/// `Module.iter_exports`.
+#[pyclass]
+pub struct ModuleExportsIterator {
+    module: wasmer::Module,
+    index: usize,
+}
+
+#[pyproto]
+impl PyIterProtocol for ModuleExportsIterator {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<Self>) -> PyResult<Option<types::ExportType>> {
+        let info = slf.module.info();
+        let mut index = slf.index;
+
+        // Skip the export holding the interruption flag, see
+        // `InterruptionHandle::is_reserved`.
+        while matches!(
+            info.exports.get_index(index),
+            Some((name, _)) if InterruptionHandle::is_reserved(name)
+        ) {
+            index += 1;
+        }
+
+        let export = match info.exports.get_index(index) {
+            Some((name, index)) => {
+                let ty = match index {
+                    wasmer_types::ExportIndex::Function(index) => wasmer::ExternType::Function(
+                        info.signatures[info.functions[*index]].clone(),
+                    ),
+                    wasmer_types::ExportIndex::Table(index) => {
+                        wasmer::ExternType::Table(info.tables[*index])
+                    }
+                    wasmer_types::ExportIndex::Memory(index) => {
+                        wasmer::ExternType::Memory(info.memories[*index])
+                    }
+                    wasmer_types::ExportIndex::Global(index) => {
+                        wasmer::ExternType::Global(info.globals[*index])
+                    }
+                };
+
+                wasmer::ExportType::new(name, ty)
+            }
+            None => return Ok(None),
+        };
+
+        slf.index = index + 1;
+
+        export.try_into().map(Some)
+    }
+}
+
+/// Lazy iterator over the imports of a `Module`, see
+/// `Module.iter_imports`.
+#[pyclass]
+pub struct ModuleImportsIterator {
+    module: wasmer::Module,
+    index: usize,
+}
+
+#[pyproto]
+impl PyIterProtocol for ModuleImportsIterator {
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<Self>) -> PyResult<Option<types::ImportType>> {
+        let info = slf.module.info();
+
+        let import = match info.imports.get_index(slf.index) {
+            Some(((namespace, name, _), index)) => {
+                let ty = match index {
+                    wasmer_types::ImportIndex::Function(index) => wasmer::ExternType::Function(
+                        info.signatures[info.functions[*index]].clone(),
+                    ),
+                    wasmer_types::ImportIndex::Table(index) => {
+                        wasmer::ExternType::Table(info.tables[*index])
+                    }
+                    wasmer_types::ImportIndex::Memory(index) => {
+                        wasmer::ExternType::Memory(info.memories[*index])
+                    }
+                    wasmer_types::ImportIndex::Global(index) => {
+                        wasmer::ExternType::Global(info.globals[*index])
+                    }
+                };
+
+                wasmer::ImportType::new(namespace, name, ty)
+            }
+            None => return Ok(None),
+        };
+
+        slf.index += 1;
+
+        import.try_into().map(Some)
     }
 }
@@ -1,8 +1,22 @@
 use crate::{
     errors::to_py_err,
+    features::Features,
+    module::Module,
     wasmer_inner::{wasmer, wasmer_engines as engines},
 };
-use pyo3::{exceptions::PyTypeError, prelude::*};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
+    prelude::*,
+};
+use sha2::{Digest, Sha256};
+use std::{
+    fs,
+    path::PathBuf,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// The identifier of the next store, see `Store.id`.
+static NEXT_STORE_ID: AtomicU64 = AtomicU64::new(0);
 
 /// The store represents all global state that can be manipulated by
 /// WebAssembly programs. It consists of the runtime representation of
@@ -42,42 +56,267 @@ use pyo3::{exceptions::PyTypeError, prelude::*};
 /// used, with the first compiler found in this order:
 /// `compiler_compiler_cranelift`, `compiler_compiler_llvm`,
 /// `compiler_compiler_singlepass`, otherwise it will run in headless
-/// mode.
+/// mode. In this case, compiling a `Module` raises a `RuntimeError`
+/// explaining that no compiler is available; pass an explicit
+/// headless engine, i.e. `Store(engine.Universal())`, to only
+/// deserialize modules.
+///
+/// Compiled modules can be cached on the disk by passing a
+/// `cache_dir`. A `Module` built from the same bytes, with the same
+/// engine, compiler configuration and features, is then loaded from
+/// the cache instead of being compiled again, e.g. in a next process:
+///
+/// ```py
+/// from wasmer import Store, Module
+///
+/// store = Store(cache_dir='/tmp/wasmer-cache')
+///
+/// # Compiled, then written in the cache.
+/// module = Module(store, '(module)')
+///
+/// # Loaded from the cache.
+/// module = Module(store, '(module)')
+/// ```
+///
+/// The cache directory must be trusted, i.e. only writable by the
+/// user running the program: the cached artifacts are native code,
+/// loaded without being validated again. They are keyed by a
+/// SHA-256 digest of the WebAssembly bytes and of the store
+/// configuration, which is checked against the header of the file
+/// before loading it, but this only protects against stale or
+/// misplaced entries, not against a malicious file.
+///
+/// With `deterministic=True`, the compiler canonicalizes the NaN
+/// values produced by floating-point operations, so that modules
+/// compute bit-for-bit identical results on every machine, which
+/// matters e.g. for consensus. It slows down floating-point heavy
+/// code, so it is disabled by default. If an engine is passed, it
+/// must have been built with `deterministic=True` too, like
+/// `engine.Universal(Compiler, deterministic=True)`.
+///
+/// ```py
+/// from wasmer import Store
+///
+/// store = Store(deterministic=True)
+///
+/// assert store.deterministic
+/// ```
+///
+/// With `interruptible=True`, the compiler instruments the code so
+/// that a call to a WebAssembly function can be interrupted by
+/// Ctrl-C, even if it never returns to the host, e.g. an infinite
+/// loop: the call then raises `KeyboardInterrupt`. It slows down the
+/// code, so it is disabled by default. Without it, Ctrl-C is only
+/// handled when the WebAssembly code calls a host function, or when
+/// the call returns. If an engine is passed, it must have been built
+/// with `interruptible=True` too.
+///
+/// ```py
+/// from wasmer import Store
+///
+/// store = Store(interruptible=True)
+///
+/// assert store.interruptible
+/// ```
 #[pyclass]
-#[pyo3(text_signature = "(engine)")]
+#[pyo3(text_signature = "(engine, /, cache_dir=None, deterministic=False, interruptible=False)")]
 pub struct Store {
     inner: wasmer::Store,
+    id: u64,
+    engine: PyObject,
     engine_name: String,
     compiler_name: Option<String>,
+    compiler_config: Option<String>,
+    features: wasmer::Features,
+    deterministic: bool,
+    interruptible: bool,
+    cache_dir: Option<PathBuf>,
+
+    // Whether the store has been built without an engine, and no
+    // compiler has been found, see `Store::check_can_compile`.
+    missing_compiler: bool,
 }
 
 impl Store {
     pub fn inner(&self) -> &wasmer::Store {
         &self.inner
     }
+
+    /// Compute the cache entry of the given module bytes, if the
+    /// store has a cache. Its key is a SHA-256 digest covering
+    /// everything that changes the artifact: the bytes, and the
+    /// configuration of the store, see `Store::config_digest`.
+    pub(crate) fn cache_entry(&self, bytes: &[u8]) -> Option<CacheEntry> {
+        let cache_dir = self.cache_dir.as_ref()?;
+        let config_digest = self.config_digest();
+
+        let mut hasher = Sha256::new();
+        hasher.update(config_digest);
+        hasher.update(bytes);
+
+        let mut key = [0; 32];
+        key.copy_from_slice(&hasher.finalize());
+
+        let file_name = key
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>();
+
+        Some(CacheEntry {
+            path: cache_dir.join(file_name).with_extension(CACHE_EXTENSION),
+            key,
+            config_digest,
+        })
+    }
+
+    /// Computes a SHA-256 digest of the configuration of the store,
+    /// i.e. of everything that changes the artifact of a module: the
+    /// version of the Wasmer runtime (see `__core_version__`) and of
+    /// this package, the engine, the compiler and its configuration,
+    /// the enabled features, and the target. It is stable across
+    /// processes, so it can be written on the disk.
+    pub(crate) fn config_digest(&self) -> [u8; 32] {
+        let description = format!(
+            "{}\n{}\n{}\n{:?}\n{:?}\n{:?}\n{:?}",
+            env!("WASMER_VERSION"),
+            env!("CARGO_PKG_VERSION"),
+            self.engine_name,
+            self.compiler_name,
+            self.compiler_config,
+            self.features,
+            self.inner.engine().target(),
+        );
+
+        let mut digest = [0; 32];
+        digest.copy_from_slice(&Sha256::digest(description.as_bytes()));
+
+        digest
+    }
+
+    /// The WebAssembly features enabled by the engine of the store.
+    pub(crate) fn enabled_features(&self) -> &wasmer::Features {
+        &self.features
+    }
+
+    /// Checks the store can compile a module. A store built without
+    /// an engine silently falls back to a headless engine when no
+    /// compiler package is installed, which can only deserialize
+    /// modules: compiling with it must fail with an actionable error.
+    pub(crate) fn check_can_compile(&self) -> PyResult<()> {
+        if self.missing_compiler {
+            return Err(to_py_err::<PyRuntimeError, _>(
+                "No compiler available; install wasmer-compiler-cranelift or pass a headless engine for deserialize-only use.",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+const CACHE_EXTENSION: &str = "wasmer";
+
+/// The prefix of the cached artifacts, followed by the cache key and
+/// the configuration digest of the store that has written them, see
+/// `CacheEntry`.
+const CACHE_MAGIC: &[u8] = b"\0wasmer-python-cache\0";
+
+/// An entry of the cache of compiled modules, see the `cache_dir`
+/// argument of `Store`.
+///
+/// The file of an entry is made of `CACHE_MAGIC`, the key, the
+/// configuration digest, and the artifact. Both digests are checked
+/// before the artifact is deserialized, so that a file can't be
+/// loaded by a store it hasn't been written for, even if it has
+/// been renamed.
+pub(crate) struct CacheEntry {
+    pub(crate) path: PathBuf,
+    key: [u8; 32],
+    config_digest: [u8; 32],
+}
+
+impl CacheEntry {
+    /// Returns the header of the file of the entry.
+    pub(crate) fn header(&self) -> Vec<u8> {
+        [CACHE_MAGIC, &self.key, &self.config_digest].concat()
+    }
+
+    /// Returns the artifact of the content of a file, if it has been
+    /// written for this entry.
+    pub(crate) fn artifact<'a>(&self, content: &'a [u8]) -> Option<&'a [u8]> {
+        content.strip_prefix(self.header().as_slice())
+    }
+}
+
+/// Checks that a store asked to be deterministic (or interruptible)
+/// is given a deterministic (or interruptible) engine: the compiler
+/// of an existing engine can't be reconfigured.
+fn check_engine_option(option: &str, requested: bool, enabled: bool) -> PyResult<bool> {
+    if requested && !enabled {
+        return Err(to_py_err::<PyValueError, _>(format!(
+            "The engine is not {}; build it with `{}=True`",
+            option, option
+        )));
+    }
+
+    Ok(enabled)
 }
 
 #[pymethods]
 impl Store {
     #[new]
-    fn new(py: Python, engine: Option<&PyAny>) -> PyResult<Self> {
-        let (inner, engine_name, compiler_name) = match engine {
+    #[args(cache_dir = "None", deterministic = "false", interruptible = "false")]
+    fn new(
+        py: Python,
+        engine: Option<&PyAny>,
+        cache_dir: Option<PathBuf>,
+        deterministic: bool,
+        interruptible: bool,
+    ) -> PyResult<Self> {
+        let without_engine = engine.is_none();
+        let (
+            inner,
+            engine,
+            engine_name,
+            compiler_name,
+            compiler_config,
+            features,
+            deterministic,
+            interruptible,
+        ) = match engine {
             Some(engine) => {
                 if let Ok(universal) = engine.downcast::<PyCell<engines::Universal>>() {
                     let universal = universal.borrow();
 
                     (
                         wasmer::Store::new(universal.inner()),
+                        engine.to_object(py),
                         engines::Universal::name(),
                         universal.compiler_name().cloned(),
+                        universal.compiler_config().cloned(),
+                        universal.features().clone(),
+                        check_engine_option(
+                            "deterministic",
+                            deterministic,
+                            universal.deterministic(),
+                        )?,
+                        check_engine_option(
+                            "interruptible",
+                            interruptible,
+                            universal.interruptible(),
+                        )?,
                     )
                 } else if let Ok(dylib) = engine.downcast::<PyCell<engines::Dylib>>() {
                     let dylib = dylib.borrow();
 
                     (
                         wasmer::Store::new(dylib.inner()),
+                        engine.to_object(py),
                         engines::Dylib::name(),
                         dylib.compiler_name().cloned(),
+                        dylib.compiler_config().cloned(),
+                        dylib.features().clone(),
+                        check_engine_option("deterministic", deterministic, dylib.deterministic())?,
+                        check_engine_option("interruptible", interruptible, dylib.interruptible())?,
                     )
                 } else {
                     return Err(to_py_err::<PyTypeError, _>("Unknown engine"));
@@ -99,30 +338,204 @@ impl Store {
                     .ok();
 
                 let target = None;
-                let engine = engines::Universal::raw_new(compiler, target)?;
+                let engine = engines::Universal::raw_new(
+                    compiler,
+                    target,
+                    None,
+                    deterministic && compiler.is_some(),
+                    interruptible && compiler.is_some(),
+                )?;
+                let inner = wasmer::Store::new(engine.inner());
+                let compiler_name = engine.compiler_name().cloned();
+                let compiler_config = engine.compiler_config().cloned();
+                let features = engine.features().clone();
+                let deterministic = engine.deterministic();
+                let interruptible = engine.interruptible();
 
                 (
-                    wasmer::Store::new(engine.inner()),
+                    inner,
+                    Py::new(py, engine)?.to_object(py),
                     engines::Universal::name(),
-                    engine.compiler_name().cloned(),
+                    compiler_name,
+                    compiler_config,
+                    features,
+                    deterministic,
+                    interruptible,
                 )
             }
         };
 
+        let missing_compiler = without_engine && compiler_name.is_none();
+
         Ok(Self {
             inner,
+            id: NEXT_STORE_ID.fetch_add(1, Ordering::SeqCst),
+            engine,
             engine_name: engine_name.to_string(),
             compiler_name,
+            compiler_config,
+            features,
+            deterministic,
+            interruptible,
+            cache_dir,
+            missing_compiler,
         })
     }
 
+    /// Returns the engine held by the store, i.e. an object of kind
+    /// `engine.Universal` or `engine.Dylib`. If the store has been
+    /// built without an engine, the default `engine.Universal` engine
+    /// is returned.
+    ///
+    /// It allows to share one engine across several stores.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import engine, Store
+    ///
+    /// store = Store()
+    ///
+    /// assert isinstance(store.engine, engine.Universal)
+    ///
+    /// other_store = Store(store.engine)
+    /// ```
+    #[getter]
+    fn engine(&self, py: Python) -> PyObject {
+        self.engine.clone_ref(py)
+    }
+
+    /// An opaque integer identifying the store. Memories, globals,
+    /// tables and functions expose a `store_id` property that can be
+    /// compared against it, to check which store they belong to.
+    ///
+    /// Each store has its own identifier, even when it shares its
+    /// engine with another store.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// memory = Memory(store, MemoryType(minimum=1))
+    ///
+    /// assert memory.store_id == store.id
+    /// assert Store().id != store.id
+    /// assert Store(store.engine).id != store.id
+    /// ```
+    #[getter]
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Compiles WebAssembly bytes, or a WAT string, into a `Module`
+    /// with this store. It is a shortcut for `Module(store, bytes)`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store, wat2wasm
+    ///
+    /// store = Store()
+    /// module = store.compile(wat2wasm('(module)'))
+    ///
+    /// assert module.exports == []
+    /// ```
+    #[pyo3(text_signature = "($self, bytes)")]
+    fn compile(slf: &PyCell<Self>, bytes: &PyAny) -> PyResult<Module> {
+        Module::new(slf, bytes)
+    }
+
+    /// The WebAssembly features enabled by the engine of the store,
+    /// as a read-only `Features` object. If the store has been built
+    /// without an engine, they are Wasmer's defaults for the selected
+    /// compiler.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store
+    ///
+    /// store = Store()
+    ///
+    /// assert store.features.simd == True
+    /// ```
+    #[getter]
+    fn features(&self) -> Features {
+        Features::raw_new(self.features.clone())
+    }
+
+    /// Whether the compiler of the store canonicalizes NaN values,
+    /// see the `deterministic` argument of `Store`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store
+    ///
+    /// assert Store().deterministic == False
+    /// ```
+    #[getter]
+    fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Whether the compiler of the store instruments the code so that
+    /// calls can be interrupted, see the `interruptible` argument of
+    /// `Store`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store
+    ///
+    /// assert Store().interruptible == False
+    /// ```
     #[getter]
-    fn engine_name(&self) -> &String {
+    fn interruptible(&self) -> bool {
+        self.interruptible
+    }
+
+    #[getter]
+    pub(crate) fn engine_name(&self) -> &String {
         &self.engine_name
     }
 
     #[getter]
-    fn compiler_name(&self) -> Option<&String> {
+    pub(crate) fn compiler_name(&self) -> Option<&String> {
         self.compiler_name.as_ref()
     }
+
+    /// Removes all the cached artifacts from the cache directory, if
+    /// the store has one (see the `cache_dir` argument of `Store`).
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Store
+    ///
+    /// store = Store(cache_dir='/tmp/wasmer-cache')
+    /// store.clear_cache()
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn clear_cache(&self) -> PyResult<()> {
+        let cache_dir = match &self.cache_dir {
+            Some(cache_dir) if cache_dir.is_dir() => cache_dir,
+            _ => return Ok(()),
+        };
+
+        for entry in fs::read_dir(cache_dir)? {
+            let path = entry?.path();
+
+            if path
+                .extension()
+                .is_some_and(|extension| extension == CACHE_EXTENSION)
+            {
+                fs::remove_file(&path)?;
+            }
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,214 @@
+use crate::wasmer_inner::{
+    wasmer::{
+        self,
+        vm::{self, MemoryError, MemoryStyle, TableStyle, VMMemoryDefinition, VMTableDefinition},
+        MemoryType, Pages, TableType, Tunables,
+    },
+    wasmer_vm,
+};
+use loupe::MemoryUsage;
+use std::{cell::Cell, error::Error, fmt, ptr::NonNull, sync::Arc};
+
+/// Tunables that cap the size of the memories they create to a given
+/// number of pages, whatever their declared maximum is. Everything
+/// else is delegated to the base tunables.
+#[derive(MemoryUsage)]
+pub(crate) struct LimitingTunables<T: Tunables> {
+    limit: Pages,
+    base: T,
+}
+
+impl<T: Tunables> LimitingTunables<T> {
+    pub(crate) fn new(base: T, limit: Pages) -> Self {
+        Self { limit, base }
+    }
+
+    /// Lowers the maximum of the memory type to the limit. A memory
+    /// without a maximum gets the limit as a maximum.
+    fn adjust_memory(&self, requested: &MemoryType) -> MemoryType {
+        let mut adjusted = requested.clone();
+        adjusted.maximum = Some(
+            requested
+                .maximum
+                .map_or(self.limit, |maximum| maximum.min(self.limit)),
+        );
+
+        adjusted
+    }
+
+    /// Checks the memory can be created within the limit.
+    fn validate_memory(&self, ty: &MemoryType) -> Result<(), MemoryError> {
+        if ty.minimum > self.limit {
+            return Err(MemoryError::Generic(format!(
+                "The memory minimum size ({} pages) exceeds `max_memory_pages` ({} pages)",
+                ty.minimum.0, self.limit.0
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: Tunables> Tunables for LimitingTunables<T> {
+    fn memory_style(&self, memory: &MemoryType) -> MemoryStyle {
+        self.base.memory_style(&self.adjust_memory(memory))
+    }
+
+    fn table_style(&self, table: &TableType) -> TableStyle {
+        self.base.table_style(table)
+    }
+
+    fn create_host_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+    ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+
+        Ok(Arc::new(CappedMemory::new(
+            self.base.create_host_memory(&adjusted, style)?,
+            ty.maximum,
+            self.limit,
+        )))
+    }
+
+    unsafe fn create_vm_memory(
+        &self,
+        ty: &MemoryType,
+        style: &MemoryStyle,
+        vm_definition_location: NonNull<VMMemoryDefinition>,
+    ) -> Result<Arc<dyn vm::Memory>, MemoryError> {
+        let adjusted = self.adjust_memory(ty);
+        self.validate_memory(&adjusted)?;
+
+        Ok(Arc::new(CappedMemory::new(
+            self.base
+                .create_vm_memory(&adjusted, style, vm_definition_location)?,
+            ty.maximum,
+            self.limit,
+        )))
+    }
+
+    fn create_host_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+    ) -> Result<Arc<dyn vm::Table>, String> {
+        self.base.create_host_table(ty, style)
+    }
+
+    unsafe fn create_vm_table(
+        &self,
+        ty: &TableType,
+        style: &TableStyle,
+        vm_definition_location: NonNull<VMTableDefinition>,
+    ) -> Result<Arc<dyn vm::Table>, String> {
+        self.base.create_vm_table(ty, style, vm_definition_location)
+    }
+}
+
+thread_local! {
+    // Whether the memories are being grown by the host, see
+    // `grow_from_host`.
+    static GROWING_FROM_HOST: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Runs `grow`, which grows a memory from the host, e.g. from
+/// `Memory.grow`. A `CappedMemory` grown beyond its cap then returns
+/// an error instead of trapping: there is no WebAssembly code to
+/// unwind.
+pub(crate) fn grow_from_host<T>(grow: impl FnOnce() -> T) -> T {
+    let growing_from_host = GROWING_FROM_HOST.with(|flag| flag.replace(true));
+    let result = grow();
+    GROWING_FROM_HOST.with(|flag| flag.set(growing_from_host));
+
+    result
+}
+
+/// A memory created by `LimitingTunables`. WebAssembly code growing it
+/// beyond the cap traps, instead of `memory.grow` returning -1, which
+/// is kept for the declared maximum of the memory.
+#[derive(Debug, MemoryUsage)]
+struct CappedMemory {
+    inner: Arc<dyn vm::Memory>,
+    declared_maximum: Option<Pages>,
+    limit: Pages,
+}
+
+impl CappedMemory {
+    fn new(inner: Arc<dyn vm::Memory>, declared_maximum: Option<Pages>, limit: Pages) -> Self {
+        Self {
+            inner,
+            declared_maximum,
+            limit,
+        }
+    }
+}
+
+/// The error of the trap raised by a `CappedMemory`.
+#[derive(Debug)]
+struct MemoryCapExceeded {
+    requested: u64,
+    limit: Pages,
+}
+
+impl fmt::Display for MemoryCapExceeded {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "The memory cannot grow to {} pages, beyond `max_memory_pages` ({} pages)",
+            self.requested, self.limit.0
+        )
+    }
+}
+
+impl Error for MemoryCapExceeded {}
+
+impl vm::Memory for CappedMemory {
+    fn ty(&self) -> MemoryType {
+        self.inner.ty()
+    }
+
+    fn style(&self) -> &MemoryStyle {
+        self.inner.style()
+    }
+
+    fn size(&self) -> Pages {
+        self.inner.size()
+    }
+
+    fn grow(&self, delta: Pages) -> Result<Pages, MemoryError> {
+        let requested = u64::from(self.inner.size().0) + u64::from(delta.0);
+        let exceeds_cap = requested > u64::from(self.limit.0);
+        let exceeds_declared_maximum = self
+            .declared_maximum
+            .is_some_and(|maximum| requested > u64::from(maximum.0));
+
+        if exceeds_cap && !exceeds_declared_maximum && !GROWING_FROM_HOST.with(|flag| flag.get()) {
+            // SAFETY: The memory is grown by WebAssembly code, see
+            // `grow_from_host`, so there is a call to unwind.
+            unsafe {
+                wasmer_vm::raise_user_trap(Box::new(MemoryCapExceeded {
+                    requested,
+                    limit: self.limit,
+                }))
+            }
+        }
+
+        self.inner.grow(delta)
+    }
+
+    fn vmmemory(&self) -> NonNull<VMMemoryDefinition> {
+        self.inner.vmmemory()
+    }
+}
+
+/// Builds a store sharing the engine of `store`, but whose memories
+/// can't grow beyond `limit`.
+pub(crate) fn limited_store(store: &wasmer::Store, limit: Pages) -> wasmer::Store {
+    let engine = store.engine();
+    let base = wasmer::BaseTunables::for_target(engine.target());
+
+    wasmer::Store::new_with_tunables(&**engine, LimitingTunables::new(base, limit))
+}
@@ -1,13 +1,18 @@
 use crate::{errors::to_py_err, wasmer_inner::wasmer};
 use pyo3::{
-    class::basic::PyObjectProtocol,
+    class::basic::{CompareOp, PyObjectProtocol},
     conversion::{FromPyObject, IntoPy},
     exceptions::PyValueError,
     prelude::*,
 };
-use std::{convert::TryFrom, slice};
+use std::{
+    collections::hash_map::DefaultHasher,
+    convert::TryFrom,
+    hash::{Hash, Hasher},
+    slice,
+};
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
 #[repr(u8)]
 pub enum Type {
     I32 = 1,
@@ -110,6 +115,19 @@ impl Into<wasmer::Type> for Type {
     }
 }
 
+/// Implements `==` and `!=` based on `PartialEq`; other comparisons
+/// are not supported.
+fn richcmp<T>(py: Python, left: &T, right: &T, op: CompareOp) -> PyObject
+where
+    T: PartialEq,
+{
+    match op {
+        CompareOp::Eq => (left == right).into_py(py),
+        CompareOp::Ne => (left != right).into_py(py),
+        _ => py.NotImplemented(),
+    }
+}
+
 /// Represents the signature of a function that is either implemented
 /// in WebAssembly module or exposed to WebAssembly by the host.
 ///
@@ -127,6 +145,7 @@ impl Into<wasmer::Type> for Type {
 /// )
 /// ```
 #[pyclass]
+#[derive(PartialEq, Hash)]
 #[pyo3(text_signature = "(params, results)")]
 pub struct FunctionType {
     /// Parameters, i.e. inputs, of the function.
@@ -144,6 +163,39 @@ impl FunctionType {
     fn new(params: Vec<Type>, results: Vec<Type>) -> Self {
         Self { params, results }
     }
+
+    /// The arity of the function, i.e. a `(number of parameters,
+    /// number of results)` tuple.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import FunctionType, Type
+    ///
+    /// function_type = FunctionType([Type.I32, Type.I32], [Type.I32])
+    ///
+    /// assert function_type.arity == (2, 1)
+    /// ```
+    #[getter]
+    fn arity(&self) -> (usize, usize) {
+        (self.params.len(), self.results.len())
+    }
+
+    /// Checks whether the function has no parameters and no results,
+    /// i.e. is a `() -> ()` function.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import FunctionType, Type
+    ///
+    /// assert FunctionType([], []).is_nullary
+    /// assert not FunctionType([Type.I32], []).is_nullary
+    /// ```
+    #[getter]
+    fn is_nullary(&self) -> bool {
+        self.params.is_empty() && self.results.is_empty()
+    }
 }
 
 impl From<&wasmer::FunctionType> for FunctionType {
@@ -180,6 +232,15 @@ impl PyObjectProtocol for FunctionType {
             self.params, self.results,
         )
     }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FunctionType(params: {:?}, results: {:?}, arity: {:?})",
+            self.params,
+            self.results,
+            self.arity(),
+        )
+    }
 }
 
 /// A descriptor for a WebAssembly memory type.
@@ -198,6 +259,7 @@ impl PyObjectProtocol for FunctionType {
 /// )
 /// ```
 #[pyclass]
+#[derive(PartialEq, Hash)]
 #[pyo3(text_signature = "(minimum, /, maximum, shared)")]
 pub struct MemoryType {
     /// The minimum number of pages in the memory.
@@ -269,6 +331,7 @@ impl PyObjectProtocol for MemoryType {
 /// global_type = GlobalType(Type.I32, mutable=False)
 /// ```
 #[pyclass]
+#[derive(PartialEq, Hash)]
 #[pyo3(text_signature = "(type, mutable)")]
 pub struct GlobalType {
     /// The type of the value stored in the global.
@@ -322,6 +385,7 @@ impl PyObjectProtocol for GlobalType {
 /// table_type = TableType(Type.I32, minimum=7, maximum=42)
 /// ```
 #[pyclass]
+#[derive(PartialEq, Hash)]
 #[pyo3(text_signature = "(type, minimum, maximum)")]
 pub struct TableType {
     /// The type of data stored in elements of the table.
@@ -347,6 +411,38 @@ impl TableType {
             maximum,
         }
     }
+
+    /// Checks whether the table stores function references, i.e. if
+    /// its type is `Type.FUNC_REF`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import TableType, Type
+    ///
+    /// assert TableType(Type.FUNC_REF, minimum=1).is_funcref == True
+    /// assert TableType(Type.EXTERN_REF, minimum=1).is_funcref == False
+    /// ```
+    #[getter]
+    fn is_funcref(&self) -> bool {
+        self.r#type == Type::FuncRef
+    }
+
+    /// Checks whether the table stores external references, i.e. if
+    /// its type is `Type.EXTERN_REF`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import TableType, Type
+    ///
+    /// assert TableType(Type.EXTERN_REF, minimum=1).is_externref == True
+    /// assert TableType(Type.FUNC_REF, minimum=1).is_externref == False
+    /// ```
+    #[getter]
+    fn is_externref(&self) -> bool {
+        self.r#type == Type::ExternRef
+    }
 }
 
 impl From<&wasmer::TableType> for TableType {
@@ -442,6 +538,38 @@ impl ExportType {
     }
 }
 
+#[pyproto]
+impl PyObjectProtocol for ExportType {
+    fn __repr__(&self) -> PyResult<String> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        Ok(format!(
+            "ExportType(name={}, type={})",
+            self.name.to_object(py).as_ref(py).repr()?,
+            self.r#type.as_ref(py).str()?,
+        ))
+    }
+
+    fn __richcmp__(&self, other: PyRef<ExportType>, op: CompareOp) -> PyResult<PyObject> {
+        let py = other.py();
+        let equal = self.name == other.name && extern_type_eq(py, &self.r#type, &other.r#type)?;
+
+        Ok(richcmp(py, &equal, &true, op))
+    }
+
+    fn __hash__(&self) -> PyResult<u64> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+        let mut hasher = DefaultHasher::new();
+
+        self.name.hash(&mut hasher);
+        extern_type_hash(py, &self.r#type, &mut hasher)?;
+
+        Ok(hasher.finish())
+    }
+}
+
 impl TryFrom<wasmer::ExportType> for ExportType {
     type Error = PyErr;
 
@@ -533,6 +661,42 @@ impl ImportType {
     }
 }
 
+#[pyproto]
+impl PyObjectProtocol for ImportType {
+    fn __repr__(&self) -> PyResult<String> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        Ok(format!(
+            "ImportType(module={}, name={}, type={})",
+            self.module.to_object(py).as_ref(py).repr()?,
+            self.name.to_object(py).as_ref(py).repr()?,
+            self.r#type.as_ref(py).str()?,
+        ))
+    }
+
+    fn __richcmp__(&self, other: PyRef<ImportType>, op: CompareOp) -> PyResult<PyObject> {
+        let py = other.py();
+        let equal = self.module == other.module
+            && self.name == other.name
+            && extern_type_eq(py, &self.r#type, &other.r#type)?;
+
+        Ok(richcmp(py, &equal, &true, op))
+    }
+
+    fn __hash__(&self) -> PyResult<u64> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+        let mut hasher = DefaultHasher::new();
+
+        self.module.hash(&mut hasher);
+        self.name.hash(&mut hasher);
+        extern_type_hash(py, &self.r#type, &mut hasher)?;
+
+        Ok(hasher.finish())
+    }
+}
+
 impl TryFrom<wasmer::ImportType> for ImportType {
     type Error = PyErr;
 
@@ -556,3 +720,51 @@ fn extern_type_to_py_object(py: Python, value: &wasmer::ExternType) -> PyResult<
         wasmer::ExternType::Memory(t) => Py::new(py, MemoryType::from(t))?.to_object(py),
     })
 }
+
+/// Compares two extern types stored as `PyObject`, i.e. two of
+/// `FunctionType`, `GlobalType`, `TableType` or `MemoryType`,
+/// structurally. Any other object is compared with `==`.
+fn extern_type_eq(py: Python, left: &PyObject, right: &PyObject) -> PyResult<bool> {
+    let (left, right) = (left.as_ref(py), right.as_ref(py));
+
+    macro_rules! structural_eq {
+        ($($extern_type:ty),*) => {
+            $(
+                if let (Ok(left), Ok(right)) = (
+                    left.extract::<PyRef<$extern_type>>(),
+                    right.extract::<PyRef<$extern_type>>(),
+                ) {
+                    return Ok(*left == *right);
+                }
+            )*
+        };
+    }
+
+    structural_eq!(FunctionType, GlobalType, TableType, MemoryType);
+
+    left.rich_compare(right, CompareOp::Eq)?.is_true()
+}
+
+/// Hashes an extern type stored as `PyObject`, consistently with
+/// `extern_type_eq`.
+fn extern_type_hash(py: Python, value: &PyObject, hasher: &mut DefaultHasher) -> PyResult<()> {
+    let value = value.as_ref(py);
+
+    macro_rules! structural_hash {
+        ($($extern_type:ty),*) => {
+            $(
+                if let Ok(value) = value.extract::<PyRef<$extern_type>>() {
+                    value.hash(hasher);
+
+                    return Ok(());
+                }
+            )*
+        };
+    }
+
+    structural_hash!(FunctionType, GlobalType, TableType, MemoryType);
+
+    value.hash()?.hash(hasher);
+
+    Ok(())
+}
@@ -1,8 +1,17 @@
-use crate::wasmer_inner::{wasmer, wasmer_types::NativeWasmType};
+use crate::{
+    errors::to_py_err,
+    externals::Function,
+    wasmer_inner::{wasmer, wasmer_types::NativeWasmType},
+};
 use pyo3::{
-    class::basic::PyObjectProtocol,
+    class::{
+        basic::{CompareOp, PyObjectProtocol},
+        number::PyNumberProtocol,
+    },
+    exceptions::PyTypeError,
     prelude::*,
     types::{PyFloat, PyLong},
+    PyNativeType,
 };
 
 pub trait NativeFromPyAny {
@@ -83,6 +92,22 @@ impl TryFromPyAny for PyAny {
 }
 
 pub(crate) fn to_wasm_value((any, ty): (&PyAny, wasmer::Type)) -> PyResult<wasmer::Value> {
+    // A `Value` is passed as is, so that its exact bits are
+    // preserved, e.g. the payload of a NaN, which may not survive a
+    // round-trip through a Python `float`.
+    if let Ok(value) = any.downcast::<PyCell<Value>>() {
+        let value = value.borrow().inner().clone();
+
+        if value.ty() != ty {
+            return Err(to_py_err::<PyTypeError, _>(format!(
+                "The value `{:?}` does not have the expected type `{:?}`",
+                value, ty
+            )));
+        }
+
+        return Ok(value);
+    }
+
     Ok(match ty {
         wasmer::Type::I32 => any
             .try_from::<i32>()
@@ -95,7 +120,16 @@ pub(crate) fn to_wasm_value((any, ty): (&PyAny, wasmer::Type)) -> PyResult<wasme
         wasmer::Type::F32 => any.try_from::<f32>()?.to_value(),
         wasmer::Type::F64 => any.try_from::<f64>()?.to_value(),
         wasmer::Type::V128 => any.try_from::<u128>()?.to_value(),
-        _ => unimplemented!(),
+        // Any Python object can be passed as an `externref`; `None`
+        // is the null reference.
+        wasmer::Type::ExternRef if any.is_none() => wasmer::Value::null(),
+        wasmer::Type::ExternRef => {
+            wasmer::Value::ExternRef(wasmer::ExternRef::new(any.to_object(any.py())))
+        }
+        wasmer::Type::FuncRef if any.is_none() => wasmer::Value::FuncRef(None),
+        wasmer::Type::FuncRef => {
+            wasmer::Value::FuncRef(Some(any.extract::<PyRef<Function>>()?.inner().clone()))
+        }
     })
 }
 
@@ -107,7 +141,16 @@ pub(crate) fn to_py_object<'p>(py: Python<'p>) -> impl Fn(&wasmer::Value) -> PyO
             wasmer::Value::F32(value) => value.to_object(py),
             wasmer::Value::F64(value) => value.to_object(py),
             wasmer::Value::V128(value) => value.to_object(py),
-            _ => unimplemented!(),
+            // An `externref` holds the Python object it has been
+            // created from, see `to_wasm_value`. A null reference, or
+            // a reference that doesn't come from Python, is `None`.
+            wasmer::Value::ExternRef(extern_ref) => extern_ref
+                .downcast::<PyObject>()
+                .map_or_else(|| py.None(), |object| object.clone_ref(py)),
+            wasmer::Value::FuncRef(Some(function)) => {
+                Function::raw_new(function.clone()).into_py(py)
+            }
+            wasmer::Value::FuncRef(None) => py.None(),
         }
     }
 }
@@ -124,15 +167,65 @@ pub(crate) fn to_py_object<'p>(py: Python<'p>) -> impl Fn(&wasmer::Value) -> PyO
 ///
 /// value = Value.i32(42)
 /// ```
+///
+/// A numeric value compares equal to the Python `int` or `float`
+/// it holds, and to the other values holding the same number:
+///
+/// ```py
+/// from wasmer import Value
+///
+/// assert Value.i32(3) == 3
+/// assert Value.f64(3.0) == 3
+/// assert Value.i64(3) == Value.i32(3)
+/// assert Value.i32(3) != '3'
+/// ```
 #[pyclass(unsendable)]
 pub struct Value {
     inner: wasmer::Value,
 }
 
 impl Value {
+    pub(crate) fn raw_new(inner: wasmer::Value) -> Self {
+        Self { inner }
+    }
+
     pub(crate) fn inner(&self) -> &wasmer::Value {
         &self.inner
     }
+
+    fn v128_from_lanes<const N: usize>(lanes: [u128; N]) -> Self {
+        let lane_width = 128 / N;
+
+        Self {
+            inner: wasmer::Value::V128(
+                lanes
+                    .iter()
+                    .enumerate()
+                    .fold(0, |v128, (nth, lane)| v128 | (lane << (nth * lane_width))),
+            ),
+        }
+    }
+
+    fn v128_lanes<const N: usize>(&self) -> PyResult<[u128; N]> {
+        let v128 = match self.inner {
+            wasmer::Value::V128(v128) => v128,
+            _ => {
+                return Err(to_py_err::<PyTypeError, _>(format!(
+                    "The value `{:?}` is not a `v128`",
+                    self.inner
+                )))
+            }
+        };
+        let lane_width = 128 / N;
+        let mask = u128::MAX >> (128 - lane_width);
+        let mut lanes = [0; N];
+
+        for (nth, lane) in lanes.iter_mut().enumerate() {
+            *lane = (v128 >> (nth * lane_width)) & mask;
+        }
+
+        Ok(lanes)
+    }
 }
 
 #[pymethods]
@@ -188,6 +281,27 @@ impl Value {
         }
     }
 
+    /// Build a WebAssembly `f32` value from its raw bits.
+    ///
+    /// Contrary to `Value.f32`, the value doesn't go through a
+    /// Python `float`, so any bit pattern, like a NaN with a specific
+    /// payload, is preserved.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.f32_from_bits(0x7fa00001)
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(bits)")]
+    fn f32_from_bits(bits: u32) -> Self {
+        Self {
+            inner: wasmer::Value::F32(f32::from_bits(bits)),
+        }
+    }
+
     /// Build a WebAssembly `f64` value.
     ///
     /// ## Example
@@ -205,6 +319,27 @@ impl Value {
         }
     }
 
+    /// Build a WebAssembly `f64` value from its raw bits.
+    ///
+    /// Contrary to `Value.f64`, the value doesn't go through a
+    /// Python `float`, so any bit pattern, like a NaN with a specific
+    /// payload, is preserved.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.f64_from_bits(0x7ff4000000000001)
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(bits)")]
+    fn f64_from_bits(bits: u64) -> Self {
+        Self {
+            inner: wasmer::Value::F64(f64::from_bits(bits)),
+        }
+    }
+
     /// Build a WebAssembly `v128` value.
     ///
     /// ## Example
@@ -221,6 +356,155 @@ impl Value {
             inner: wasmer::Value::V128(value),
         }
     }
+
+    /// Build a WebAssembly `v128` value from 4 `i32` lanes.
+    ///
+    /// Lanes are packed following the WebAssembly little-endian
+    /// convention, i.e. the first lane occupies the lowest bits.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.v128_from_i32x4(1, 2, 3, 4)
+    ///
+    /// assert value.as_i32x4() == (1, 2, 3, 4)
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(a, b, c, d)")]
+    fn v128_from_i32x4(a: i32, b: i32, c: i32, d: i32) -> Self {
+        Self::v128_from_lanes([a, b, c, d].map(|lane| lane as u32 as u128))
+    }
+
+    /// Build a WebAssembly `v128` value from 2 `i64` lanes.
+    ///
+    /// Lanes are packed following the WebAssembly little-endian
+    /// convention, i.e. the first lane occupies the lowest bits.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.v128_from_i64x2(1, 2)
+    ///
+    /// assert value.as_i64x2() == (1, 2)
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(a, b)")]
+    fn v128_from_i64x2(a: i64, b: i64) -> Self {
+        Self::v128_from_lanes([a, b].map(|lane| lane as u64 as u128))
+    }
+
+    /// Build a WebAssembly `v128` value from 4 `f32` lanes.
+    ///
+    /// Lanes are packed following the WebAssembly little-endian
+    /// convention, i.e. the first lane occupies the lowest bits.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// value = Value.v128_from_f32x4(1.5, 2.5, 3.5, 4.5)
+    ///
+    /// assert value.as_f32x4() == (1.5, 2.5, 3.5, 4.5)
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(a, b, c, d)")]
+    fn v128_from_f32x4(a: f32, b: f32, c: f32, d: f32) -> Self {
+        Self::v128_from_lanes([a, b, c, d].map(|lane| lane.to_bits() as u128))
+    }
+
+    /// Unpack a WebAssembly `v128` value into 4 `i32` lanes.
+    ///
+    /// Raises `TypeError` if the value isn't a `v128`. See
+    /// `Value.v128_from_i32x4` to see an example.
+    #[pyo3(text_signature = "($self)")]
+    fn as_i32x4(&self) -> PyResult<(i32, i32, i32, i32)> {
+        let [a, b, c, d] = self.v128_lanes::<4>()?.map(|lane| lane as u32 as i32);
+
+        Ok((a, b, c, d))
+    }
+
+    /// Unpack a WebAssembly `v128` value into 2 `i64` lanes.
+    ///
+    /// Raises `TypeError` if the value isn't a `v128`. See
+    /// `Value.v128_from_i64x2` to see an example.
+    #[pyo3(text_signature = "($self)")]
+    fn as_i64x2(&self) -> PyResult<(i64, i64)> {
+        let [a, b] = self.v128_lanes::<2>()?.map(|lane| lane as u64 as i64);
+
+        Ok((a, b))
+    }
+
+    /// Unpack a WebAssembly `v128` value into 4 `f32` lanes.
+    ///
+    /// Raises `TypeError` if the value isn't a `v128`. See
+    /// `Value.v128_from_f32x4` to see an example.
+    #[pyo3(text_signature = "($self)")]
+    fn as_f32x4(&self) -> PyResult<(f32, f32, f32, f32)> {
+        let [a, b, c, d] = self
+            .v128_lanes::<4>()?
+            .map(|lane| f32::from_bits(lane as u32));
+
+        Ok((a, b, c, d))
+    }
+
+    /// Checks whether the value is a float (`f32` or `f64`) which is
+    /// NaN. It returns `False` for any other type.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// assert Value.f64(float('nan')).is_nan()
+    /// assert not Value.f32(4.2).is_nan()
+    /// assert not Value.i32(42).is_nan()
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn is_nan(&self) -> bool {
+        match self.inner() {
+            wasmer::Value::F32(value) => value.is_nan(),
+            wasmer::Value::F64(value) => value.is_nan(),
+            _ => false,
+        }
+    }
+
+    /// Checks whether the value is a float (`f32` or `f64`) which is
+    /// positive or negative infinity. It returns `False` for any
+    /// other type.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import Value
+    ///
+    /// assert Value.f32(float('-inf')).is_infinite()
+    /// assert not Value.f64(4.2).is_infinite()
+    /// assert not Value.i64(42).is_infinite()
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn is_infinite(&self) -> bool {
+        match self.inner() {
+            wasmer::Value::F32(value) => value.is_infinite(),
+            wasmer::Value::F64(value) => value.is_infinite(),
+            _ => false,
+        }
+    }
+}
+
+impl Value {
+    /// Returns the number held by the value as a Python object, or
+    /// `None` for references.
+    fn to_number(&self, py: Python) -> Option<PyObject> {
+        match self.inner() {
+            wasmer::Value::ExternRef(_) | wasmer::Value::FuncRef(_) => None,
+            value => Some(to_py_object(py)(value)),
+        }
+    }
 }
 
 #[pyproto]
@@ -228,4 +512,65 @@ impl PyObjectProtocol for Value {
     fn __repr__(&self) -> PyResult<String> {
         Ok(format!("{:?}", self.inner()))
     }
+
+    fn __richcmp__(&self, other: &PyAny, op: CompareOp) -> PyResult<PyObject> {
+        let py = other.py();
+
+        let number = match self.to_number(py) {
+            Some(number) => number,
+            None => return Ok(py.NotImplemented()),
+        };
+
+        let other = match other.downcast::<PyCell<Value>>() {
+            Ok(other) => match other.borrow().to_number(py) {
+                Some(other) => other,
+                None => return Ok(py.NotImplemented()),
+            },
+            Err(_) => other.to_object(py),
+        };
+
+        Ok(number.as_ref(py).rich_compare(other, op)?.to_object(py))
+    }
+
+    fn __hash__(&self) -> PyResult<isize> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        match self.to_number(py) {
+            Some(number) => number.as_ref(py).hash(),
+            None => Err(to_py_err::<PyTypeError, _>(format!(
+                "Cannot hash a value of type `{:?}`",
+                self.inner().ty()
+            ))),
+        }
+    }
+}
+
+#[pyproto]
+impl PyNumberProtocol for Value {
+    fn __int__(&self) -> PyResult<PyObject> {
+        let gil_guard = Python::acquire_gil();
+        let py = gil_guard.python();
+
+        match self.inner() {
+            wasmer::Value::I32(value) => Ok(value.to_object(py)),
+            wasmer::Value::I64(value) => Ok(value.to_object(py)),
+            wasmer::Value::V128(value) => Ok(value.to_object(py)),
+            value => Err(to_py_err::<PyTypeError, _>(format!(
+                "Cannot convert a value of type `{:?}` to `int`",
+                value.ty()
+            ))),
+        }
+    }
+
+    fn __float__(&self) -> PyResult<f64> {
+        match self.inner() {
+            wasmer::Value::F32(value) => Ok(f64::from(*value)),
+            wasmer::Value::F64(value) => Ok(*value),
+            value => Err(to_py_err::<PyTypeError, _>(format!(
+                "Cannot convert a value of type `{:?}` to `float`",
+                value.ty()
+            ))),
+        }
+    }
 }
@@ -1,13 +1,26 @@
 use crate::{
-    errors::to_py_err, import_object::ImportObject, module::Module, store::Store,
-    wasmer_inner::wasmer_wasi,
+    errors::to_py_err,
+    externals::Memory,
+    import_object::ImportObject,
+    module::Module,
+    store::Store,
+    wasmer_inner::{wasmer, wasmer_vfs, wasmer_wasi},
 };
 use pyo3::{
-    exceptions::{PyRuntimeError, PyValueError},
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
     prelude::*,
-    types::{PyDict, PyList},
+    types::{PyBytes, PyDict, PyList},
+};
+use std::{
+    cell::Cell,
+    convert::TryFrom,
+    fs,
+    io::{Read, Write},
+    iter,
+    path::{Path, PathBuf},
+    slice,
+    sync::{Arc, Mutex},
 };
-use std::{path::PathBuf, slice};
 
 #[derive(Copy, Clone)]
 #[repr(u8)]
@@ -103,19 +116,37 @@ impl Into<wasmer_wasi::WasiVersion> for Version {
 )]
 pub struct StateBuilder {
     inner: wasmer_wasi::WasiStateBuilder,
+
+    // The program name, the arguments and the environment variables
+    // are tracked here, and not in `inner`, as the latter doesn't
+    // support resetting them. They are applied by `finalize`.
+    program_name: String,
+    arguments: Vec<String>,
+    environments: Vec<(String, String)>,
+
+    // The limits of the program, see `StateBuilder.limits`.
+    limits: Limits,
+}
+
+/// The limits of a WASI program, see `StateBuilder.limits`.
+#[derive(Clone, Copy, Default)]
+struct Limits {
+    max_open_fds: Option<u32>,
+    max_memory_pages: Option<u32>,
 }
 
 impl StateBuilder {
     pub fn self_arguments(&mut self, arguments: &PyList) {
-        self.inner.args(arguments.iter().map(ToString::to_string));
+        self.arguments
+            .extend(arguments.iter().map(ToString::to_string));
     }
 
     pub fn self_argument(&mut self, argument: String) {
-        self.inner.arg(argument);
+        self.arguments.push(argument);
     }
 
     pub fn self_environments(&mut self, environments: &PyDict) {
-        self.inner.envs(
+        self.environments.extend(
             environments
                 .iter()
                 .map(|(any_key, any_value)| (any_key.to_string(), any_value.to_string())),
@@ -123,42 +154,146 @@ impl StateBuilder {
     }
 
     pub fn self_environment(&mut self, key: String, value: String) {
-        self.inner.env(key, value);
+        self.environments.push((key, value));
     }
 
     pub fn self_preopen_directories(&mut self, preopen_directories: &PyList) -> PyResult<()> {
+        let preopen_directories = preopen_directories
+            .iter()
+            .map(|any_item| any_item.extract::<PathBuf>())
+            .collect::<PyResult<Vec<_>>>()?;
+
         self.inner
-            .preopen_dirs(
-                preopen_directories
-                    .iter()
-                    .map(|any_item| PathBuf::from(any_item.to_string())),
-            )
+            .preopen_dirs(preopen_directories)
             .map_err(to_py_err::<PyRuntimeError, _>)?;
 
         Ok(())
     }
 
-    pub fn self_preopen_directory(&mut self, preopen_directory: String) -> PyResult<()> {
+    pub fn self_preopen_directory(&mut self, preopen_directory: PathBuf) -> PyResult<()> {
         self.inner
-            .preopen_dir(PathBuf::from(preopen_directory))
+            .preopen_dir(preopen_directory)
             .map_err(to_py_err::<PyRuntimeError, _>)?;
 
         Ok(())
     }
 
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    pub fn self_preopen_fd(&mut self, fd: i32, guest_path: String, writable: bool) -> PyResult<()> {
+        use pyo3::exceptions::PyNotADirectoryError;
+        use std::io;
+
+        // The underlying builder can only preopen paths, which are
+        // resolved on each access of the program. On Linux, an open
+        // directory is reachable through `/dev/fd`, without going
+        // through its original path. A duplicate of the file
+        // descriptor is preopened, so that the caller can close
+        // theirs. The duplicate is never closed: the program may
+        // access it as long as the process lives, and a reused file
+        // descriptor would expose another file.
+        let duplicate = unsafe { libc::fcntl(fd, libc::F_DUPFD_CLOEXEC, 0) };
+
+        if duplicate < 0 {
+            return Err(io::Error::last_os_error().into());
+        }
+
+        let path = PathBuf::from(format!("/dev/fd/{}", duplicate));
+
+        let preopened = match fs::metadata(&path) {
+            Ok(metadata) if metadata.is_dir() => self
+                .inner
+                .preopen(|preopen| {
+                    preopen
+                        .directory(&path)
+                        .alias(guest_path.as_str())
+                        .read(true)
+                        .write(writable)
+                        .create(writable)
+                })
+                .map(|_| ())
+                .map_err(to_py_err::<PyRuntimeError, _>),
+            Ok(_) => Err(to_py_err::<PyNotADirectoryError, _>(format!(
+                "The file descriptor `{}` does not refer to a directory",
+                fd
+            ))),
+            Err(error) => Err(error.into()),
+        };
+
+        if preopened.is_err() {
+            unsafe { libc::close(duplicate) };
+        }
+
+        preopened
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    pub fn self_preopen_fd(
+        &mut self,
+        _fd: i32,
+        _guest_path: String,
+        _writable: bool,
+    ) -> PyResult<()> {
+        use pyo3::exceptions::PyNotImplementedError;
+
+        Err(to_py_err::<PyNotImplementedError, _>(
+            "Preopening a file descriptor requires `/dev/fd` to expose directories, which is only the case on Linux",
+        ))
+    }
+
     pub fn self_map_directories(&mut self, map_directories: &PyDict) -> PyResult<()> {
+        let map_directories = map_directories
+            .iter()
+            .map(|(any_key, any_value)| Ok((any_key.to_string(), any_value.extract::<PathBuf>()?)))
+            .collect::<PyResult<Vec<_>>>()?;
+
+        self.inner
+            .map_dirs(map_directories)
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        Ok(())
+    }
+
+    pub fn self_virtual_fs(&mut self, files: &PyDict) -> PyResult<()> {
+        use wasmer_vfs::FileSystem as _;
+
+        let file_system = wasmer_vfs::mem_fs::FileSystem::default();
+
+        for (any_path, any_contents) in files.iter() {
+            let path = Path::new("/").join(any_path.extract::<PathBuf>()?);
+            let contents = any_contents.extract::<&[u8]>()?;
+
+            // Create the parent directories, from the outermost one.
+            let mut parents = path.ancestors().skip(1).collect::<Vec<_>>();
+            parents.reverse();
+
+            for parent in parents.into_iter().skip(1) {
+                match file_system.create_dir(parent) {
+                    Ok(()) | Err(wasmer_vfs::FsError::AlreadyExists) => (),
+                    Err(error) => return Err(to_py_err::<PyRuntimeError, _>(error)),
+                }
+            }
+
+            file_system
+                .new_open_options()
+                .write(true)
+                .create(true)
+                .truncate(true)
+                .open(&path)
+                .map_err(to_py_err::<PyRuntimeError, _>)?
+                .write_all(contents)?;
+        }
+
         self.inner
-            .map_dirs(map_directories.iter().map(|(any_key, any_value)| {
-                (any_key.to_string(), PathBuf::from(any_value.to_string()))
-            }))
+            .set_fs(Box::new(file_system))
+            .preopen(|preopen| preopen.directory("/").read(true).write(true).create(true))
             .map_err(to_py_err::<PyRuntimeError, _>)?;
 
         Ok(())
     }
 
-    pub fn self_map_directory(&mut self, alias: String, directory: String) -> PyResult<()> {
+    pub fn self_map_directory(&mut self, alias: String, directory: PathBuf) -> PyResult<()> {
         self.inner
-            .map_dir(alias.as_str(), PathBuf::from(directory))
+            .map_dir(alias.as_str(), directory)
             .map_err(to_py_err::<PyRuntimeError, _>)?;
 
         Ok(())
@@ -177,6 +312,10 @@ impl StateBuilder {
     ) -> PyResult<Self> {
         let mut wasi = Self {
             inner: wasmer_wasi::WasiState::new(program_name.as_str()),
+            program_name,
+            arguments: Vec::new(),
+            environments: Vec::new(),
+            limits: Limits::default(),
         };
 
         if let Some(arguments) = arguments {
@@ -198,6 +337,51 @@ impl StateBuilder {
         Ok(wasi)
     }
 
+    /// Creates a state builder preconfigured to run an untrusted
+    /// program against a single directory:
+    ///
+    /// * `root_dir` is the only preopened directory, mapped to `/`,
+    ///   and it is read-only,
+    /// * no environment variable is set, the host environment is
+    ///   never inherited,
+    /// * the standard output and error are captured instead of being
+    ///   written to the host's, see `Environment.read_stdout` and
+    ///   `Environment.read_stderr`.
+    ///
+    /// More configuration can be chained, e.g. to map a writable
+    /// directory with `map_directory`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    ///
+    /// wasi_env = \
+    ///     wasi.StateBuilder.sandboxed('test-program', '.'). \
+    ///         argument('--foo'). \
+    ///         finalize()
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(program_name, root_dir)")]
+    pub fn sandboxed(program_name: String, root_dir: PathBuf) -> PyResult<Self> {
+        let mut wasi = Self::new(program_name, None, None, None, None)?;
+
+        wasi.inner
+            .preopen(|preopen| {
+                preopen
+                    .directory(&root_dir)
+                    .alias("/")
+                    .read(true)
+                    .write(false)
+                    .create(false)
+            })
+            .map_err(to_py_err::<PyRuntimeError, _>)?
+            .stdout(Box::new(wasmer_wasi::Pipe::new()))
+            .stderr(Box::new(wasmer_wasi::Pipe::new()));
+
+        Ok(wasi)
+    }
+
     /// Add multiple arguments.
     ///
     /// Arguments must not contain the nul (`0x0`) byte.
@@ -248,11 +432,60 @@ impl StateBuilder {
         Ok(slf)
     }
 
+    /// Remove all the arguments added so far. The program name is
+    /// kept. It allows to reuse a builder with different arguments.
+    ///
+    /// This method returns `self`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    ///
+    /// wasi_state_builder = \
+    ///     wasi.StateBuilder('test-program'). \
+    ///         argument('--verbose'). \
+    ///         clear_arguments(). \
+    ///         argument('--help')
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    pub fn clear_arguments(slf: &PyCell<Self>) -> PyResult<&PyCell<Self>> {
+        slf.try_borrow_mut()?.arguments.clear();
+
+        Ok(slf)
+    }
+
+    /// Get or set the program name, i.e. the first argument
+    /// (`argv[0]`) received by the WASI module.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    ///
+    /// wasi_state_builder = wasi.StateBuilder('test-program')
+    /// wasi_state_builder.program_name = 'other-program'
+    ///
+    /// assert wasi_state_builder.program_name == 'other-program'
+    /// ```
+    #[getter]
+    fn program_name(&self) -> &str {
+        &self.program_name
+    }
+
+    #[setter(program_name)]
+    fn set_program_name(&mut self, program_name: String) {
+        self.program_name = program_name;
+    }
+
     /// Add environment variable pairs.
     ///
     /// Environment variable keys and values must not contain the byte
     /// `=` (`0x3d`) or null (`0x0`).
     ///
+    /// The pairs are added to the ones already set, they don't
+    /// replace them. Use `clear_environments` to start over.
+    ///
     /// This method returns `self`.
     ///
     /// ## Example
@@ -304,6 +537,29 @@ impl StateBuilder {
         Ok(slf)
     }
 
+    /// Remove all the environment variables added so far. It allows
+    /// to reuse a builder with different environment variables.
+    ///
+    /// This method returns `self`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    ///
+    /// wasi_state_builder = \
+    ///     wasi.StateBuilder('test-program'). \
+    ///         environment("ABC", "DEF"). \
+    ///         clear_environments(). \
+    ///         environment("X", "YZ")
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    pub fn clear_environments(slf: &PyCell<Self>) -> PyResult<&PyCell<Self>> {
+        slf.try_borrow_mut()?.environments.clear();
+
+        Ok(slf)
+    }
+
     /// Preopen directories.
     ///
     /// This opens the given directories at the virtual root, `/`, and
@@ -336,7 +592,8 @@ impl StateBuilder {
     ///
     /// This opens the given directory at the virtual root, `/`, and
     /// allows the WASI module to read and write to the given
-    /// directory.
+    /// directory. The directory can be a `str` or any `os.PathLike`
+    /// object, such as `pathlib.Path`.
     ///
     /// This method returns `self`.
     ///
@@ -352,7 +609,7 @@ impl StateBuilder {
     #[pyo3(text_signature = "($self, preopen_directory)")]
     pub fn preopen_directory<'py>(
         slf: &'py PyCell<Self>,
-        preopen_directory: String,
+        preopen_directory: PathBuf,
     ) -> PyResult<&'py PyCell<Self>> {
         let mut slf_mut = slf.try_borrow_mut()?;
         slf_mut.self_preopen_directory(preopen_directory)?;
@@ -360,6 +617,51 @@ impl StateBuilder {
         Ok(slf)
     }
 
+    /// Preopen an already opened directory file descriptor, e.g. from
+    /// `os.open(path, os.O_DIRECTORY)`, and expose it to the WASI
+    /// module as `guest_path`. The WASI module can only read the
+    /// directory, unless `writable` is true, in which case it can
+    /// also write and create files in it.
+    ///
+    /// It is useful when the directory can't be re-opened by its path
+    /// anymore, e.g. after permissions have been dropped. The file
+    /// descriptor is duplicated, so it can be closed once this method
+    /// returns; the duplicate stays open until the process exits.
+    ///
+    /// It is only supported on Linux, where `/dev/fd` exposes the
+    /// open directories; it raises `NotImplementedError` on other
+    /// platforms. An invalid file descriptor raises `OSError`.
+    ///
+    /// This method returns `self`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    /// import os
+    ///
+    /// fd = os.open(".", os.O_RDONLY)
+    ///
+    /// wasi_state_builder = \
+    ///     wasi.StateBuilder('test-program'). \
+    ///         preopen_fd(fd, "foo", writable=True)
+    ///
+    /// os.close(fd)
+    /// ```
+    #[pyo3(text_signature = "($self, fd, guest_path, /, writable=False)")]
+    #[args(writable = "false")]
+    pub fn preopen_fd<'py>(
+        slf: &'py PyCell<Self>,
+        fd: i32,
+        guest_path: String,
+        writable: bool,
+    ) -> PyResult<&'py PyCell<Self>> {
+        let mut slf_mut = slf.try_borrow_mut()?;
+        slf_mut.self_preopen_fd(fd, guest_path, writable)?;
+
+        Ok(slf)
+    }
+
     /// Preopen directories with different names exposed to the WASI.
     ///
     /// This method returns `self`.
@@ -401,7 +703,7 @@ impl StateBuilder {
     pub fn map_directory<'py>(
         slf: &'py PyCell<Self>,
         alias: String,
-        directory: String,
+        directory: PathBuf,
     ) -> PyResult<&'py PyCell<Self>> {
         let mut slf_mut = slf.try_borrow_mut()?;
         slf_mut.self_map_directory(alias, directory)?;
@@ -409,6 +711,108 @@ impl StateBuilder {
         Ok(slf)
     }
 
+    /// Replaces the host filesystem by an in-memory filesystem,
+    /// holding the given files, and preopens its root, `/`. `files` is
+    /// a dictionary mapping guest paths (like `/data/input.txt`) to
+    /// `bytes`; the parent directories are created. The WASI module
+    /// can open and read these files as if they were preopened from
+    /// the host, which gives hermetic, reproducible runs.
+    ///
+    /// The WASI module can also write: the writes stay in memory, and
+    /// are discarded with the environment. Once this method is
+    /// called, the host filesystem isn't reachable anymore, including
+    /// from directories preopened with other methods.
+    ///
+    /// This method returns `self`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    ///
+    /// wasi_state_builder = \
+    ///     wasi.StateBuilder('test-program'). \
+    ///         virtual_fs({'/data/input.txt': b'Hello, World!'})
+    /// ```
+    #[pyo3(text_signature = "($self, files)")]
+    pub fn virtual_fs<'py>(slf: &'py PyCell<Self>, files: &PyDict) -> PyResult<&'py PyCell<Self>> {
+        let mut slf_mut = slf.try_borrow_mut()?;
+        slf_mut.self_virtual_fs(files)?;
+
+        Ok(slf)
+    }
+
+    /// Bounds the resources the WASI program may use:
+    ///
+    /// * `max_open_fds` is the maximum number of open file
+    ///   descriptors, including the standard input, output and error,
+    ///   the root directory, and the preopened directories. It is
+    ///   enforced by WASI: once reached, opening a file
+    ///   (`path_open`) fails with the `EMFILE` errno, which the
+    ///   program sees like any other error,
+    /// * `max_memory_bytes` is the maximum size of the memories
+    ///   defined by the program, rounded down to whole WebAssembly
+    ///   pages. It is enforced by the store tunables, when the
+    ///   import object generated by the environment is used to
+    ///   create an `Instance`: growing a memory beyond the limit
+    ///   traps when done by the program (`memory.grow`), and fails
+    ///   when done with `Memory.grow`, like with the
+    ///   `max_memory_pages` argument of `Instance`. A memory whose
+    ///   minimum size exceeds the limit fails to be instantiated.
+    ///   Only the `ImportObject`s returned by
+    ///   `Environment.generate_import_object` and
+    ///   `StateBuilder.build_import_object` carry the limit: a
+    ///   dictionary can't, so `Environment.generate_imports` raises
+    ///   a `ValueError`.
+    ///
+    /// `None` keeps the matching resource unbounded. This method
+    /// returns `self`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi, Store, Module, Instance
+    ///
+    /// store = Store()
+    /// module = Module(store, open('tests/wasi.wasm', 'rb').read())
+    ///
+    /// import_object, wasi_env = \
+    ///     wasi.StateBuilder('test-program'). \
+    ///         limits(max_open_fds=16, max_memory_bytes=64 * 1024 * 1024). \
+    ///         build_import_object(store, module)
+    ///
+    /// instance = Instance(module, import_object)
+    /// ```
+    #[args(max_open_fds = "None", max_memory_bytes = "None")]
+    #[pyo3(text_signature = "($self, max_open_fds=None, max_memory_bytes=None)")]
+    pub fn limits(
+        slf: &PyCell<Self>,
+        max_open_fds: Option<u32>,
+        max_memory_bytes: Option<u64>,
+    ) -> PyResult<&PyCell<Self>> {
+        let max_memory_pages = max_memory_bytes
+            .map(|max_memory_bytes| {
+                u32::try_from(max_memory_bytes / wasmer::WASM_PAGE_SIZE as u64)
+                    .ok()
+                    .filter(|pages| *pages <= wasmer::WASM_MAX_PAGES)
+                    .ok_or_else(|| {
+                        to_py_err::<PyValueError, _>(format!(
+                            "`max_memory_bytes` must be at most {}, got {}",
+                            u64::from(wasmer::WASM_MAX_PAGES) * wasmer::WASM_PAGE_SIZE as u64,
+                            max_memory_bytes
+                        ))
+                    })
+            })
+            .transpose()?;
+
+        slf.try_borrow_mut()?.limits = Limits {
+            max_open_fds,
+            max_memory_pages,
+        };
+
+        Ok(slf)
+    }
+
     /// Produces a WASI `Environment` based on this state builder.
     ///
     /// ## Example
@@ -423,11 +827,92 @@ impl StateBuilder {
     /// ```
     #[pyo3(text_signature = "($self)")]
     pub fn finalize(&mut self) -> PyResult<Environment> {
-        Ok(Environment::raw_new(
-            self.inner
-                .finalize()
-                .map_err(to_py_err::<PyRuntimeError, _>)?,
-        ))
+        let arguments = iter::once(&self.program_name)
+            .chain(&self.arguments)
+            .map(|argument| {
+                if argument.contains('\0') {
+                    Err(to_py_err::<PyRuntimeError, _>(format!(
+                        "The argument `{}` contains the nul byte",
+                        argument.escape_default()
+                    )))
+                } else {
+                    Ok(argument.clone().into_bytes())
+                }
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let environments = self
+            .environments
+            .iter()
+            .map(|(key, value)| {
+                if key.contains('=') || key.contains('\0') || value.contains('\0') {
+                    Err(to_py_err::<PyRuntimeError, _>(format!(
+                        "The environment variable `{}={}` is invalid: the key must not contain `=` or the nul byte, and the value must not contain the nul byte",
+                        key.escape_default(),
+                        value.escape_default()
+                    )))
+                } else {
+                    Ok(format!("{}={}", key, value).into_bytes())
+                }
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        let environment = self
+            .inner
+            .finalize()
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        {
+            let mut state = environment.state();
+            state.args = arguments;
+            state.envs = environments;
+        }
+
+        let mut environment = Environment::raw_new(environment);
+        environment.limits = self.limits;
+
+        Ok(environment)
+    }
+
+    /// Finalizes the state builder, like `finalize`, and generates the
+    /// import object for the WASI version used by `module`, like
+    /// `Environment.generate_import_object`. It returns a tuple of the
+    /// import object and the environment, so that the environment
+    /// can still be updated, e.g. to set its memory.
+    ///
+    /// A `RuntimeError` is raised if the module doesn't use WASI.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi, Store, Module, Instance
+    ///
+    /// store = Store()
+    /// module = Module(store, open('tests/wasi.wasm', 'rb').read())
+    ///
+    /// import_object, wasi_env = \
+    ///     wasi.StateBuilder('test-program'). \
+    ///         argument('--foo'). \
+    ///         build_import_object(store, module)
+    ///
+    /// instance = Instance(module, import_object)
+    /// ```
+    #[pyo3(text_signature = "($self, store, module)")]
+    pub fn build_import_object(
+        &mut self,
+        store: &Store,
+        module: &Module,
+    ) -> PyResult<(ImportObject, Environment)> {
+        let wasi_version = get_version(module, false).ok_or_else(|| {
+            to_py_err::<PyRuntimeError, _>(
+                "Cannot detect the WASI version of the module: it does not import any WASI namespace",
+            )
+        })?;
+
+        let environment = self.finalize()?;
+        let import_object = environment.generate_import_object(store, wasi_version)?;
+
+        Ok((import_object, environment))
     }
 }
 
@@ -438,12 +923,282 @@ impl StateBuilder {
 #[pyclass(unsendable)]
 pub struct Environment {
     inner: wasmer_wasi::WasiEnv,
+    exit_code: Arc<Mutex<Option<u32>>>,
+    limits: Limits,
+
+    // The memory set with `Environment.memory`, if any, see
+    // `Environment::wasi_env`.
+    memory: Option<wasmer::Memory>,
+
+    // The identifier of the store the environment is used with,
+    // once known, i.e. once imports have been generated or a memory
+    // has been set.
+    store_id: Cell<Option<u64>>,
 }
 
 impl Environment {
     fn raw_new(inner: wasmer_wasi::WasiEnv) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            exit_code: Arc::new(Mutex::new(None)),
+            limits: Limits::default(),
+            memory: None,
+            store_id: Cell::new(None),
+        }
+    }
+
+    /// Returns the WASI environment to give to the imports, with the
+    /// memory set with `Environment.memory`, if any.
+    ///
+    /// `WasiEnv` has no memory setter: its memory is initialized from
+    /// the `memory` export of an instance, once. So the memory is set
+    /// on a copy of the environment, through a minimal instance
+    /// exporting it.
+    fn wasi_env(&self) -> PyResult<wasmer_wasi::WasiEnv> {
+        let mut wasi_env = self.inner.clone();
+
+        if let Some(memory) = &self.memory {
+            let module = wasmer::Module::new(
+                memory.store(),
+                r#"(module (import "env" "memory" (memory 0)) (export "memory" (memory 0)))"#,
+            )
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+            let instance = wasmer::Instance::new(
+                &module,
+                &wasmer::imports! {
+                    "env" => {
+                        "memory" => memory.clone(),
+                    },
+                },
+            )
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+            wasmer::WasmerEnv::init_with_instance(&mut wasi_env, &instance)
+                .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+            // The environment only holds a weak reference to the
+            // instance exporting the memory, which is dropped here,
+            // whereas a clone holds a strong one.
+            return Ok(wasi_env.clone());
+        }
+
+        Ok(wasi_env)
+    }
+
+    /// Checks the environment is used with a single store, given the
+    /// identifier of the store of a new memory or imports.
+    fn check_store_id(&self, store_id: u64) -> PyResult<()> {
+        match self.store_id.get() {
+            Some(expected_store_id) if expected_store_id != store_id => {
+                Err(to_py_err::<PyRuntimeError, _>(
+                    "The WASI environment is already used with another store",
+                ))
+            }
+            _ => {
+                self.store_id.set(Some(store_id));
+
+                Ok(())
+            }
+        }
+    }
+
+    /// Replaces the `proc_exit` imports of `import_object` by a
+    /// function that records the exit code in `self.exit_code`,
+    /// before exiting like the original `proc_exit`.
+    fn record_exit_code(
+        &self,
+        store: &wasmer::Store,
+        import_object: wasmer::ImportObject,
+    ) -> wasmer::ImportObject {
+        #[derive(wasmer::WasmerEnv, Clone)]
+        struct ProcExitEnvironment {
+            exit_code: Arc<Mutex<Option<u32>>>,
+        }
+
+        let proc_exit = wasmer::Function::new_with_env(
+            store,
+            wasmer::FunctionType::new(vec![wasmer::Type::I32], vec![]),
+            ProcExitEnvironment {
+                exit_code: self.exit_code.clone(),
+            },
+            |environment,
+             arguments: &[wasmer::Value]|
+             -> Result<Vec<wasmer::Value>, wasmer::RuntimeError> {
+                let exit_code = arguments[0].unwrap_i32() as u32;
+
+                if let Ok(mut recorded_exit_code) = environment.exit_code.lock() {
+                    *recorded_exit_code = Some(exit_code);
+                }
+
+                Err(wasmer::RuntimeError::user(Box::new(
+                    wasmer_wasi::WasiError::Exit(exit_code),
+                )))
+            },
+        );
+
+        map_imports(import_object, |name, export| {
+            if name == "proc_exit" {
+                wasmer::Extern::Function(proc_exit.clone())
+            } else {
+                export
+            }
+        })
+    }
+
+    /// Replaces the `path_open` imports of `import_object` by a
+    /// function failing with `EMFILE` when the program has already
+    /// opened `max_open_fds` file descriptors, and opening the path
+    /// like the original `path_open` otherwise.
+    fn limit_open_fds(
+        &self,
+        store: &wasmer::Store,
+        wasi_env: &wasmer_wasi::WasiEnv,
+        wasi_version: wasmer_wasi::WasiVersion,
+        import_object: wasmer::ImportObject,
+    ) -> wasmer::ImportObject {
+        let max_open_fds = match self.limits.max_open_fds {
+            Some(max_open_fds) => max_open_fds as usize,
+            None => return import_object,
+        };
+
+        map_imports(import_object, |name, export| match export {
+            wasmer::Extern::Function(path_open) if name == "path_open" => {
+                wasmer::Extern::Function(wasmer::Function::new_with_env(
+                    store,
+                    path_open.ty().clone(),
+                    PathOpenEnvironment {
+                        wasi_env: wasi_env.clone(),
+                        wasi_version,
+                        max_open_fds,
+                        path_open: None,
+                    },
+                    |environment,
+                     arguments: &[wasmer::Value]|
+                     -> Result<Vec<wasmer::Value>, wasmer::RuntimeError> {
+                        let open_fds = environment.wasi_env.state().fs.fd_map.len();
+
+                        if open_fds >= environment.max_open_fds {
+                            return Ok(vec![wasmer::Value::I32(i32::from(
+                                wasmer_wasi::types::__WASI_EMFILE,
+                            ))]);
+                        }
+
+                        environment
+                            .path_open
+                            .as_ref()
+                            .ok_or_else(|| {
+                                wasmer::RuntimeError::new("`path_open` is not initialized")
+                            })?
+                            .call(arguments)
+                            .map(|results| results.into_vec())
+                    },
+                ))
+            }
+            export => export,
+        })
+    }
+}
+
+/// The environment of the `path_open` import limiting the number of
+/// open file descriptors, see `Environment::limit_open_fds`.
+#[derive(Clone)]
+struct PathOpenEnvironment {
+    wasi_env: wasmer_wasi::WasiEnv,
+    wasi_version: wasmer_wasi::WasiVersion,
+    max_open_fds: usize,
+
+    // The original `path_open`, created once the environment has been
+    // initialized with the instance, see `init_with_instance`.
+    path_open: Option<wasmer::Function>,
+}
+
+/// A module calling the imported `path_open` from its own
+/// `path_open`, and re-exporting the imported memory for the
+/// environment of the imported `path_open`, see
+/// `PathOpenEnvironment::init_with_instance`.
+const PATH_OPEN_FORWARDER: &str = r#"
+(module
+  (import "env" "memory" (memory 0))
+  (import "env" "path_open"
+    (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+  (export "memory" (memory 0))
+  (func (export "path_open") (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)
+    (call $path_open
+      (local.get 0) (local.get 1) (local.get 2) (local.get 3) (local.get 4)
+      (local.get 5) (local.get 6) (local.get 7) (local.get 8))))
+"#;
+
+impl wasmer::WasmerEnv for PathOpenEnvironment {
+    /// The original `path_open` is a native function, which can't be
+    /// called from the host, and it needs the memory of the instance.
+    /// So it is imported, along with the memory, by an instance of
+    /// `PATH_OPEN_FORWARDER`, whose `path_open` is called instead.
+    fn init_with_instance(
+        &mut self,
+        instance: &wasmer::Instance,
+    ) -> Result<(), wasmer::HostEnvInitError> {
+        let store = instance.store();
+        let memory = instance.exports.get_memory("memory")?;
+        let path_open = wasmer_wasi::generate_import_object_from_env(
+            store,
+            self.wasi_env.clone(),
+            self.wasi_version,
+        )
+        .externs_vec()
+        .into_iter()
+        .find_map(|(_, name, export)| match export {
+            wasmer::Extern::Function(function) if name == "path_open" => Some(function),
+            _ => None,
+        })
+        .expect("WASI imports `path_open`");
+
+        let module = wasmer::Module::new(store, PATH_OPEN_FORWARDER)
+            .expect("`PATH_OPEN_FORWARDER` is a valid module");
+        let forwarder = wasmer::Instance::new(
+            &module,
+            &wasmer::imports! {
+                "env" => {
+                    "memory" => memory.clone(),
+                    "path_open" => path_open,
+                },
+            },
+        )
+        .expect("`PATH_OPEN_FORWARDER` imports `path_open` and a memory");
+
+        self.path_open = Some(forwarder.exports.get_function("path_open")?.clone());
+
+        Ok(())
+    }
+}
+
+/// Rebuilds `import_object`, where each import is replaced by the
+/// result of `map`, given the import name and the import itself.
+fn map_imports<F>(import_object: wasmer::ImportObject, map: F) -> wasmer::ImportObject
+where
+    F: Fn(&str, wasmer::Extern) -> wasmer::Extern,
+{
+    let mut namespaces: Vec<(String, wasmer::Exports)> = Vec::new();
+
+    for (namespace, name, export) in import_object.externs_vec() {
+        let export = map(&name, export);
+
+        match namespaces.iter_mut().find(|(n, _)| *n == namespace) {
+            Some((_, exports)) => exports.insert(name, export),
+            None => {
+                let mut exports = wasmer::Exports::new();
+                exports.insert(name, export);
+                namespaces.push((namespace, exports));
+            }
+        }
+    }
+
+    let mut import_object = wasmer::ImportObject::new();
+
+    for (namespace, exports) in namespaces {
+        import_object.register(namespace, exports);
     }
+
+    import_object
 }
 
 #[pymethods]
@@ -465,14 +1220,28 @@ impl Environment {
     /// import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
     /// ```
     //#[pyo3(text_signature = "($self, store, wasi_version)")]
-    fn generate_import_object(&self, store: &Store, wasi_version: Version) -> ImportObject {
+    fn generate_import_object(
+        &self,
+        store: &Store,
+        wasi_version: Version,
+    ) -> PyResult<ImportObject> {
+        self.check_store_id(store.id())?;
+
+        let wasi_env = self.wasi_env()?;
         let import_object = wasmer_wasi::generate_import_object_from_env(
             store.inner(),
-            self.inner.clone(),
+            wasi_env.clone(),
             wasi_version.into(),
         );
 
-        ImportObject::raw_new(import_object)
+        let import_object = self.record_exit_code(store.inner(), import_object);
+        let import_object =
+            self.limit_open_fds(store.inner(), &wasi_env, wasi_version.into(), import_object);
+
+        Ok(
+            ImportObject::raw_new(import_object)
+                .with_max_memory_pages(self.limits.max_memory_pages),
+        )
     }
 
     /// Create a dictionary of import with an existing
@@ -491,12 +1260,200 @@ impl Environment {
     /// wasi_env = wasi.StateBuilder('test-program').argument('--foo').finalize()
     /// imports = wasi_env.generate_imports(store, wasi.Version.SNAPSHOT1)
     /// ```
+    ///
+    /// A `ValueError` is raised if the memories are limited (see
+    /// `StateBuilder.limits`), as a dictionary can't carry the limit;
+    /// use `generate_import_object` instead.
     //#[pyo3(text_signature = "($self, store, wasi_version)")]
     fn generate_imports(&self, store: &Store, wasi_version: Version) -> PyResult<PyObject> {
-        self.generate_import_object(store, wasi_version).to_dict()
+        if self.limits.max_memory_pages.is_some() {
+            return Err(to_py_err::<PyValueError, _>(
+                "The memories are limited, which a dictionary of imports can't enforce; use `generate_import_object` instead",
+            ));
+        }
+
+        self.generate_import_object(store, wasi_version)?.to_dict()
     }
+
+    /// Sets the memory used by the WASI functions, as a `Memory`
+    /// object, instead of the memory exported by the instance. It
+    /// must be done before generating the imports, see
+    /// `generate_import_object`. It requires a compiler, see
+    /// `Store`.
+    ///
+    /// A `TypeError` is raised if the value isn't a `Memory`, and a
+    /// `RuntimeError` if the memory belongs to another store than
+    /// the one the environment is used with.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi, Store, Memory, MemoryType
+    ///
+    /// store = Store()
+    /// wasi_env = wasi.StateBuilder('test-program').finalize()
+    /// wasi_env.memory = Memory(store, MemoryType(minimum=1))
+    ///
+    /// import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
+    /// ```
+    #[setter]
+    fn set_memory(&mut self, memory: &PyAny) -> PyResult<()> {
+        let memory = match memory.downcast::<PyCell<Memory>>() {
+            Ok(memory) => memory,
+            Err(_) => {
+                return Err(to_py_err::<PyTypeError, _>(format!(
+                    "The WASI environment memory must be a `Memory`, given `{}`",
+                    memory.get_type().name()?
+                )))
+            }
+        };
+        let memory = memory.borrow();
+
+        // The store of a memory read from an `ImportObject` isn't
+        // known, so it can't be checked.
+        if let Some(store_id) = memory.store_id(memory.py()) {
+            self.check_store_id(store_id)?;
+        }
+
+        self.memory = Some(memory.inner().clone());
+
+        Ok(())
+    }
+
+    /// Returns the exit code the WASI program has passed to
+    /// `proc_exit`, or `None` if it hasn't exited that way (yet).
+    ///
+    /// Exiting with `proc_exit` stops the execution by raising a
+    /// `RuntimeError`; this method allows to read the exit code once
+    /// the error is caught. The exit code is recorded by the imports
+    /// generated by `generate_import_object` or `generate_imports`.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi, Store, Module, Instance
+    ///
+    /// store = Store()
+    /// module = Module(
+    ///     store,
+    ///     """
+    ///     (module
+    ///       (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+    ///       (memory (export "memory") 1)
+    ///       (func (export "_start")
+    ///         i32.const 42
+    ///         call $proc_exit))
+    ///     """
+    /// )
+    ///
+    /// wasi_env = wasi.StateBuilder('test-program').finalize()
+    /// import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
+    /// instance = Instance(module, import_object)
+    ///
+    /// assert wasi_env.get_exit_code() == None
+    ///
+    /// try:
+    ///     instance.exports._start()
+    /// except RuntimeError:
+    ///     pass
+    ///
+    /// assert wasi_env.get_exit_code() == 42
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn get_exit_code(&self) -> Option<u32> {
+        self.exit_code.lock().ok().and_then(|exit_code| *exit_code)
+    }
+
+    /// Reads, and consumes, what the program has written on its
+    /// standard output so far, if it is captured (see
+    /// `StateBuilder.sandboxed`). Otherwise, the standard output is
+    /// the host's, and empty bytes are returned.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import wasi
+    ///
+    /// wasi_env = wasi.StateBuilder.sandboxed('test-program', '.').finalize()
+    ///
+    /// assert wasi_env.read_stdout() == b''
+    /// ```
+    #[pyo3(text_signature = "($self)")]
+    fn read_stdout<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        let mut state = self.inner.state();
+        let stdout = state
+            .fs
+            .stdout_mut()
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        Ok(read_captured(py, stdout.as_deref_mut()))
+    }
+
+    /// Reads, and consumes, what the program has written on its
+    /// standard error so far, if it is captured. See
+    /// `Environment.read_stdout`.
+    #[pyo3(text_signature = "($self)")]
+    fn read_stderr<'py>(&self, py: Python<'py>) -> PyResult<&'py PyBytes> {
+        let mut state = self.inner.state();
+        let stderr = state
+            .fs
+            .stderr_mut()
+            .map_err(to_py_err::<PyRuntimeError, _>)?;
+
+        Ok(read_captured(py, stderr.as_deref_mut()))
+    }
+}
+
+/// Reads the bytes of a captured standard stream, i.e. a pipe. The
+/// host's standard streams can't be read, and give empty bytes.
+fn read_captured<'py, R: Read + ?Sized>(py: Python<'py>, file: Option<&mut R>) -> &'py PyBytes {
+    let mut bytes = Vec::new();
+
+    if let Some(file) = file {
+        if file.read_to_end(&mut bytes).is_err() {
+            bytes.clear();
+        }
+    }
+
+    PyBytes::new(py, &bytes)
 }
 
 pub fn get_version(module: &Module, strict: bool) -> Option<Version> {
     wasmer_wasi::get_wasi_version(&module.inner(), strict).map(Into::into)
 }
+
+pub fn detect_version(bytes: &[u8], strict: bool) -> PyResult<Option<Version>> {
+    let mut namespaces = Vec::new();
+
+    for payload in wasmparser::Parser::new(0).parse_all(bytes) {
+        if let wasmparser::Payload::ImportSection(imports) =
+            payload.map_err(to_py_err::<PyValueError, _>)?
+        {
+            for import in imports {
+                let import = import.map_err(to_py_err::<PyValueError, _>)?;
+
+                if let wasmparser::ImportSectionEntryType::Function(_) = import.ty {
+                    namespaces.push(import.module);
+                }
+            }
+        }
+    }
+
+    // Same rules as `wasmer_wasi::get_wasi_version`.
+    let namespace_version = |namespace: &str| match namespace {
+        "wasi_unstable" => Some(Version::Snapshot0),
+        "wasi_snapshot_preview1" => Some(Version::Snapshot1),
+        _ => None,
+    };
+
+    Ok(if strict {
+        match namespaces.split_first() {
+            Some((first, rest)) if rest.iter().all(|namespace| namespace == first) => {
+                namespace_version(first)
+            }
+            _ => None,
+        }
+    } else {
+        namespaces.into_iter().find_map(namespace_version)
+    })
+}
@@ -1,12 +1,241 @@
 use crate::errors::to_py_err;
-use pyo3::{exceptions::PyRuntimeError, prelude::*, types::PyBytes};
+use pyo3::{
+    buffer::PyBuffer,
+    exceptions::{PyRuntimeError, PyTypeError, PyValueError},
+    import_exception,
+    prelude::*,
+    types::{IntoPyDict, PyBytes, PyString, PyTuple},
+};
 
-pub fn wat2wasm<'py>(py: Python<'py>, wat: String) -> PyResult<&'py PyBytes> {
-    wat::parse_str(wat)
-        .map(|bytes| PyBytes::new(py, bytes.as_slice()))
-        .map_err(to_py_err::<PyRuntimeError, _>)
+// The exception is created by `register_exceptions` when the
+// `wasmer` module is initialized, and is looked up from it.
+import_exception!(wasmer, WatError);
+
+/// Create the `WatError` exception, and add it to `module`.
+///
+/// It inherits from the builtin `ValueError`, and from the builtin
+/// `RuntimeError` too, which was raised for parse errors before, so
+/// that the existing `except RuntimeError` handlers keep working.
+pub fn register_exceptions(py: Python, module: &PyModule) -> PyResult<()> {
+    let builtins = py.import("builtins")?;
+    let exception = builtins.getattr("type")?.call1((
+        "WatError",
+        PyTuple::new(
+            py,
+            [
+                builtins.getattr("ValueError")?,
+                builtins.getattr("RuntimeError")?,
+            ],
+        ),
+        [
+            ("__module__", "wasmer"),
+            (
+                "__doc__",
+                "Raised when a WAT source fails to be parsed. The `line` and `column` attributes (starting at 1) locate the error, and `text` is the offending line.",
+            ),
+        ]
+        .into_py_dict(py),
+    ))?;
+
+    module.add("WatError", exception)
+}
+
+/// Parses a WAT source into WebAssembly bytes. A parse error is
+/// turned into a `WatError` locating the error in the source.
+fn parse_wat(py: Python, wat: &str) -> PyResult<Vec<u8>> {
+    let to_wat_error = |mut error: wast::Error| -> PyErr {
+        let (line, column) = error.span().linecol_in(wat);
+        error.set_text(wat);
+
+        let wat_error = WatError::new_err(error.to_string());
+        let value = wat_error.pvalue(py);
+
+        for (name, attribute) in [
+            ("line", (line + 1).to_object(py)),
+            ("column", (column + 1).to_object(py)),
+            ("text", wat.lines().nth(line).unwrap_or("").to_object(py)),
+        ] {
+            if let Err(error) = value.setattr(name, attribute) {
+                return error;
+            }
+        }
+
+        wat_error
+    };
+
+    let buffer = wast::parser::ParseBuffer::new(wat).map_err(to_wat_error)?;
+    let mut module = wast::parser::parse::<wast::Wat>(&buffer)
+        .map_err(to_wat_error)?
+        .module;
+
+    module.encode().map_err(to_wat_error)
+}
+
+pub fn wat2wasm<'py>(
+    py: Python<'py>,
+    wat: &PyAny,
+    generate_name_section: bool,
+) -> PyResult<&'py PyBytes> {
+    let wat = if let Ok(string) = wat.downcast::<PyString>() {
+        string.to_str()?.to_string()
+    } else if let Ok(buffer) = PyBuffer::<u8>::get(wat) {
+        String::from_utf8(buffer.to_vec(py)?).map_err(|error| {
+            to_py_err::<PyValueError, _>(format!("The WAT source is not valid UTF-8: {}", error))
+        })?
+    } else {
+        return Err(to_py_err::<PyTypeError, _>(
+            "`wat2wasm` accepts a WAT string, or WAT bytes (any object implementing the buffer protocol)",
+        ));
+    };
+
+    let bytes = parse_wat(py, &wat)?;
+    let bytes = if generate_name_section {
+        bytes
+    } else {
+        strip_name_section(&bytes).map_err(to_py_err::<PyRuntimeError, _>)?
+    };
+
+    Ok(PyBytes::new(py, bytes.as_slice()))
+}
+
+/// Copies a WebAssembly module without its `name` custom section,
+/// i.e. the section holding the identifiers of the WAT source.
+fn strip_name_section(bytes: &[u8]) -> Result<Vec<u8>, wasmparser::BinaryReaderError> {
+    strip_custom_sections(bytes, |name| name != "name")
+}
+
+/// Copies a WebAssembly module without its custom sections, except
+/// the ones whose name satisfies `keep`. The other sections are
+/// copied as is.
+pub(crate) fn strip_custom_sections<F>(
+    bytes: &[u8],
+    keep: F,
+) -> Result<Vec<u8>, wasmparser::BinaryReaderError>
+where
+    F: Fn(&str) -> bool,
+{
+    const HEADER_SIZE: usize = 8;
+
+    if bytes.len() < HEADER_SIZE {
+        return Ok(bytes.to_vec());
+    }
+
+    let mut output = bytes[..HEADER_SIZE].to_vec();
+    let mut reader = wasmparser::BinaryReader::new_with_offset(&bytes[HEADER_SIZE..], HEADER_SIZE);
+
+    while !reader.eof() {
+        let section_start = reader.original_position();
+        let id = reader.read_u8()?;
+        let size = reader.read_var_u32()? as usize;
+        let payload = reader.read_bytes(size)?;
+
+        let is_stripped = id == 0
+            && !wasmparser::BinaryReader::new(payload)
+                .read_string()
+                .is_ok_and(&keep);
+
+        if !is_stripped {
+            output.extend_from_slice(&bytes[section_start..reader.original_position()]);
+        }
+    }
+
+    Ok(output)
 }
 
 pub fn wasm2wat(bytes: &PyBytes) -> PyResult<String> {
     wasmprinter::print_bytes(bytes.as_bytes()).map_err(to_py_err::<PyRuntimeError, _>)
 }
+
+/// Normalizes the text format printed by `wasm2wat`: comments are
+/// removed, whitespace is collapsed, and each line is re-indented
+/// according to its nesting level. String literals are kept as is.
+pub fn canonicalize(wat: &str) -> String {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    let mut line_depth = 0;
+    let mut depth = 0usize;
+    let mut pending_space = false;
+    let mut chars = wat.chars().peekable();
+
+    let mut end_line = |line: &mut String, line_depth: usize| {
+        if !line.is_empty() {
+            lines.push(format!("{}{}", "  ".repeat(line_depth), line));
+            line.clear();
+        }
+    };
+
+    while let Some(character) = chars.next() {
+        match character {
+            // Block comment, possibly nested.
+            '(' if chars.peek() == Some(&';') => {
+                chars.next();
+                let mut comment_depth = 1;
+
+                while comment_depth > 0 {
+                    match (chars.next(), chars.peek()) {
+                        (Some('('), Some(';')) => {
+                            chars.next();
+                            comment_depth += 1;
+                        }
+                        (Some(';'), Some(')')) => {
+                            chars.next();
+                            comment_depth -= 1;
+                        }
+                        (None, _) => break,
+                        _ => {}
+                    }
+                }
+
+                pending_space = true;
+            }
+
+            // Line comment.
+            ';' if chars.peek() == Some(&';') => {
+                while chars.peek().is_some_and(|next| *next != '\n') {
+                    chars.next();
+                }
+            }
+
+            '\n' => {
+                end_line(&mut line, line_depth);
+                line_depth = depth;
+                pending_space = false;
+            }
+
+            character if character.is_whitespace() => pending_space = true,
+
+            _ => {
+                if pending_space && !line.is_empty() && !line.ends_with('(') && character != ')' {
+                    line.push(' ');
+                }
+
+                pending_space = false;
+                line.push(character);
+
+                match character {
+                    '(' => depth += 1,
+                    ')' => depth = depth.saturating_sub(1),
+
+                    // String literal, copied verbatim.
+                    '"' => {
+                        while let Some(character) = chars.next() {
+                            line.push(character);
+
+                            match character {
+                                '\\' => line.extend(chars.next()),
+                                '"' => break,
+                                _ => {}
+                            }
+                        }
+                    }
+
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    end_line(&mut line, line_depth);
+
+    lines.join("\n")
+}
@@ -1,6 +1,10 @@
-use crate::target_lexicon::Target;
-use pyo3::{exceptions::PyRuntimeError, prelude::*};
-use std::mem::ManuallyDrop;
+use crate::{interruption::Interruption, target_lexicon::Target};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyValueError},
+    prelude::*,
+    types::PyDict,
+};
+use std::{mem::ManuallyDrop, sync::Arc};
 
 /// Universal engine for Wasmer compilers.
 ///
@@ -11,17 +15,87 @@ use std::mem::ManuallyDrop;
 ///
 /// It is possible to specify a `Target` to possibly cross-compile for
 /// a different target. It requires a compiler.
+///
+/// The WebAssembly features, i.e. the enabled WebAssembly proposals,
+/// default to the ones of the compiler for the target. They can be
+/// changed with `features`, either a `dict` mapping proposal names to
+/// booleans, or an object with the proposal names as attributes,
+/// like `Store.features`. It requires a compiler too.
+///
+/// With `deterministic=True`, the compiler canonicalizes the NaN
+/// values produced by floating-point operations, so that a module
+/// computes bit-for-bit identical results on every machine. It
+/// requires a compiler, and it slows down floating-point heavy code,
+/// so it is disabled by default.
+///
+/// With `interruptible=True`, the compiler instruments the code so
+/// that a call can be interrupted while it runs WebAssembly code,
+/// e.g. an infinite loop, by Ctrl-C: the call then raises
+/// `KeyboardInterrupt`. It requires a compiler too, and it slows
+/// down the code, so it is disabled by default.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import engine, Store
+/// from wasmer_compiler_cranelift import Compiler
+///
+/// store = Store(engine.Universal(Compiler, features={'simd': False}))
+///
+/// assert store.features.simd == False
+/// ```
 #[pyclass(unsendable, subclass)]
-#[pyo3(text_signature = "(/, compiler, target)")]
+#[pyo3(
+    text_signature = "(/, compiler, target, features, deterministic=False, interruptible=False)"
+)]
 pub struct Universal {
     inner: wasmer::UniversalEngine,
     compiler_name: Option<String>,
+    compiler_config: Option<String>,
+    features: wasmer::Features,
+    deterministic: bool,
+    interruptible: bool,
 }
 
 impl Universal {
-    pub fn raw_new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        let (inner, compiler_name) = match compiler {
-            None => (wasmer::Universal::headless().engine(), None),
+    pub fn raw_new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        features: Option<&PyAny>,
+        deterministic: bool,
+        interruptible: bool,
+    ) -> PyResult<Self> {
+        let (inner, compiler_name, compiler_config, features) = match compiler {
+            None => {
+                if features.is_some() {
+                    return Err(PyValueError::new_err(
+                        "Setting `features` requires a compiler; a headless engine cannot compile",
+                    ));
+                }
+
+                if deterministic {
+                    return Err(PyValueError::new_err(
+                        "Setting `deterministic` requires a compiler; a headless engine cannot compile",
+                    ));
+                }
+
+                if interruptible {
+                    return Err(PyValueError::new_err(
+                        "Setting `interruptible` requires a compiler; a headless engine cannot compile",
+                    ));
+                }
+
+                let features = wasmer::Features::default();
+
+                (
+                    wasmer::Universal::headless()
+                        .features(features.clone())
+                        .engine(),
+                    None,
+                    None,
+                    features,
+                )
+            }
             Some(compiler) => {
                 let opaque_compiler = compiler.call_method0("into_opaque_compiler")?;
                 let opaque_compiler_inner_ptr = opaque_compiler
@@ -42,15 +116,34 @@ impl Universal {
                 // SAFETY: `ManuallyDrop::take` semantically moves out the contained value. The
                 // danger here is when the container is used by someone else. It doesn't happen in
                 // this codebase.
-                let compiler_config =
+                let mut compiler_config =
                     unsafe { ManuallyDrop::take(&mut opaque_compiler_inner_ref.compiler_config) };
 
-                let mut engine_builder = wasmer::Universal::new(compiler_config);
+                if deterministic {
+                    compiler_config.canonicalize_nans(true);
+                }
 
-                if let Some(target) = target {
-                    engine_builder = engine_builder.target(target.inner().clone());
+                if interruptible {
+                    compiler_config.push_middleware(interruption_middleware());
                 }
 
+                // Use the compiler's default features for the
+                // target, explicitly, so that they can be queried.
+                let target = target
+                    .map(|target| target.inner().clone())
+                    .unwrap_or_default();
+                let features = match features {
+                    Some(features) => merge_features(
+                        compiler_config.default_features_for_target(&target),
+                        features,
+                    )?,
+                    None => compiler_config.default_features_for_target(&target),
+                };
+
+                let engine_builder = wasmer::Universal::new(compiler_config)
+                    .target(target)
+                    .features(features.clone());
+
                 (
                     engine_builder.engine(),
                     Some(
@@ -59,6 +152,12 @@ impl Universal {
                             .extract::<String>()
                             .map_err(PyErr::from)?,
                     ),
+                    Some(compiler_description(
+                        compiler,
+                        deterministic,
+                        interruptible,
+                    )?),
+                    features,
                 )
             }
         };
@@ -66,6 +165,10 @@ impl Universal {
         Ok(Self {
             inner,
             compiler_name,
+            compiler_config,
+            features,
+            deterministic,
+            interruptible,
         })
     }
 
@@ -80,13 +183,42 @@ impl Universal {
     pub fn compiler_name(&self) -> Option<&String> {
         self.compiler_name.as_ref()
     }
+
+    /// A description of the compiler configuration, i.e. the
+    /// representation of the compiler, if any.
+    pub fn compiler_config(&self) -> Option<&String> {
+        self.compiler_config.as_ref()
+    }
+
+    /// The WebAssembly features enabled by the engine.
+    pub fn features(&self) -> &wasmer::Features {
+        &self.features
+    }
+
+    /// Whether the compiler canonicalizes NaN values.
+    pub fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Whether the compiler instruments the code to be interrupted,
+    /// see `interruption_middleware`.
+    pub fn interruptible(&self) -> bool {
+        self.interruptible
+    }
 }
 
 #[pymethods]
 impl Universal {
     #[new]
-    fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        Self::raw_new(compiler, target)
+    #[args(deterministic = "false", interruptible = "false")]
+    fn new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        features: Option<&PyAny>,
+        deterministic: bool,
+        interruptible: bool,
+    ) -> PyResult<Self> {
+        Self::raw_new(compiler, target, features, deterministic, interruptible)
     }
 }
 
@@ -101,17 +233,87 @@ impl Universal {
 ///
 /// It is possible to specify a `Target` to possibly cross-compile for
 /// a different target. It requires a compiler.
+///
+/// The WebAssembly features, i.e. the enabled WebAssembly proposals,
+/// default to the ones of the compiler for the target. They can be
+/// changed with `features`, either a `dict` mapping proposal names to
+/// booleans, or an object with the proposal names as attributes,
+/// like `Store.features`. It requires a compiler too.
+///
+/// With `deterministic=True`, the compiler canonicalizes the NaN
+/// values produced by floating-point operations, so that a module
+/// computes bit-for-bit identical results on every machine. It
+/// requires a compiler, and it slows down floating-point heavy code,
+/// so it is disabled by default.
+///
+/// With `interruptible=True`, the compiler instruments the code so
+/// that a call can be interrupted while it runs WebAssembly code,
+/// e.g. an infinite loop, by Ctrl-C: the call then raises
+/// `KeyboardInterrupt`. It requires a compiler too, and it slows
+/// down the code, so it is disabled by default.
+///
+/// ## Example
+///
+/// ```py
+/// from wasmer import engine, Store
+/// from wasmer_compiler_cranelift import Compiler
+///
+/// store = Store(engine.Dylib(Compiler, features={'simd': False}))
+///
+/// assert store.features.simd == False
+/// ```
 #[pyclass(unsendable, subclass)]
-#[pyo3(text_signature = "(/, compiler, target)")]
+#[pyo3(
+    text_signature = "(/, compiler, target, features, deterministic=False, interruptible=False)"
+)]
 pub struct Dylib {
     inner: wasmer::DylibEngine,
     compiler_name: Option<String>,
+    compiler_config: Option<String>,
+    features: wasmer::Features,
+    deterministic: bool,
+    interruptible: bool,
 }
 
 impl Dylib {
-    pub fn raw_new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        let (inner, compiler_name) = match compiler {
-            None => (wasmer::Dylib::headless().engine(), None),
+    pub fn raw_new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        features: Option<&PyAny>,
+        deterministic: bool,
+        interruptible: bool,
+    ) -> PyResult<Self> {
+        let (inner, compiler_name, compiler_config, features) = match compiler {
+            None => {
+                if features.is_some() {
+                    return Err(PyValueError::new_err(
+                        "Setting `features` requires a compiler; a headless engine cannot compile",
+                    ));
+                }
+
+                if deterministic {
+                    return Err(PyValueError::new_err(
+                        "Setting `deterministic` requires a compiler; a headless engine cannot compile",
+                    ));
+                }
+
+                if interruptible {
+                    return Err(PyValueError::new_err(
+                        "Setting `interruptible` requires a compiler; a headless engine cannot compile",
+                    ));
+                }
+
+                let features = wasmer::Features::default();
+
+                (
+                    wasmer::Dylib::headless()
+                        .features(features.clone())
+                        .engine(),
+                    None,
+                    None,
+                    features,
+                )
+            }
             Some(compiler) => {
                 let opaque_compiler = compiler.call_method0("into_opaque_compiler")?;
                 let opaque_compiler_inner_ptr = opaque_compiler
@@ -132,15 +334,34 @@ impl Dylib {
                 // SAFETY: `ManuallyDrop::take` semantically moves out the contained value. The
                 // danger here is when the container is used by someone else. It doesn't happen in
                 // this codebase.
-                let compiler_config =
+                let mut compiler_config =
                     unsafe { ManuallyDrop::take(&mut opaque_compiler_inner_ref.compiler_config) };
 
-                let mut engine_builder = wasmer::Dylib::new(compiler_config);
+                if deterministic {
+                    compiler_config.canonicalize_nans(true);
+                }
 
-                if let Some(target) = target {
-                    engine_builder = engine_builder.target(target.inner().clone());
+                if interruptible {
+                    compiler_config.push_middleware(interruption_middleware());
                 }
 
+                // Use the compiler's default features for the
+                // target, explicitly, so that they can be queried.
+                let target = target
+                    .map(|target| target.inner().clone())
+                    .unwrap_or_default();
+                let features = match features {
+                    Some(features) => merge_features(
+                        compiler_config.default_features_for_target(&target),
+                        features,
+                    )?,
+                    None => compiler_config.default_features_for_target(&target),
+                };
+
+                let engine_builder = wasmer::Dylib::new(compiler_config)
+                    .target(target)
+                    .features(features.clone());
+
                 (
                     engine_builder.engine(),
                     Some(
@@ -149,6 +370,12 @@ impl Dylib {
                             .extract::<String>()
                             .map_err(PyErr::from)?,
                     ),
+                    Some(compiler_description(
+                        compiler,
+                        deterministic,
+                        interruptible,
+                    )?),
+                    features,
                 )
             }
         };
@@ -156,6 +383,10 @@ impl Dylib {
         Ok(Self {
             inner,
             compiler_name,
+            compiler_config,
+            features,
+            deterministic,
+            interruptible,
         })
     }
 
@@ -170,14 +401,113 @@ impl Dylib {
     pub fn compiler_name(&self) -> Option<&String> {
         self.compiler_name.as_ref()
     }
+
+    /// A description of the compiler configuration, i.e. the
+    /// representation of the compiler, if any.
+    pub fn compiler_config(&self) -> Option<&String> {
+        self.compiler_config.as_ref()
+    }
+
+    /// The WebAssembly features enabled by the engine.
+    pub fn features(&self) -> &wasmer::Features {
+        &self.features
+    }
+
+    /// Whether the compiler canonicalizes NaN values.
+    pub fn deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    /// Whether the compiler instruments the code to be interrupted,
+    /// see `interruption_middleware`.
+    pub fn interruptible(&self) -> bool {
+        self.interruptible
+    }
 }
 
 #[pymethods]
 impl Dylib {
     #[new]
-    fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<Self> {
-        Self::raw_new(compiler, target)
+    #[args(deterministic = "false", interruptible = "false")]
+    fn new(
+        compiler: Option<&PyAny>,
+        target: Option<&Target>,
+        features: Option<&PyAny>,
+        deterministic: bool,
+        interruptible: bool,
+    ) -> PyResult<Self> {
+        Self::raw_new(compiler, target, features, deterministic, interruptible)
+    }
+}
+
+/// Overrides the `defaults` features with `features`, which is either
+/// a `dict` mapping proposal names to booleans, or an object with the
+/// proposal names as attributes (missing attributes keep their
+/// default value).
+fn merge_features(defaults: wasmer::Features, features: &PyAny) -> PyResult<wasmer::Features> {
+    let mut merged = defaults;
+    let mut flags = [
+        ("threads", &mut merged.threads),
+        ("reference_types", &mut merged.reference_types),
+        ("simd", &mut merged.simd),
+        ("bulk_memory", &mut merged.bulk_memory),
+        ("multi_value", &mut merged.multi_value),
+        ("tail_call", &mut merged.tail_call),
+        ("module_linking", &mut merged.module_linking),
+        ("multi_memory", &mut merged.multi_memory),
+        ("memory64", &mut merged.memory64),
+        ("exceptions", &mut merged.exceptions),
+    ];
+
+    if let Ok(features) = features.downcast::<PyDict>() {
+        for (name, value) in features {
+            let name = name.extract::<&str>()?;
+            let flag = flags
+                .iter_mut()
+                .find(|(flag_name, _)| *flag_name == name)
+                .ok_or_else(|| {
+                    PyValueError::new_err(format!("The WebAssembly feature `{}` is unknown", name))
+                })?;
+
+            *flag.1 = value.extract()?;
+        }
+    } else {
+        for (name, flag) in flags.iter_mut() {
+            if let Ok(value) = features.getattr(*name) {
+                **flag = value.extract()?;
+            }
+        }
     }
+
+    Ok(merged)
+}
+
+/// Describes the compiler configuration with the representation of
+/// the compiler. Canonicalizing NaNs and instrumenting the
+/// code for interruptions change the generated code, so they are
+/// part of the description.
+fn compiler_description(
+    compiler: &PyAny,
+    deterministic: bool,
+    interruptible: bool,
+) -> PyResult<String> {
+    let mut description = compiler.repr()?.to_str()?.to_string();
+
+    if deterministic {
+        description.push_str(" (deterministic)");
+    }
+
+    if interruptible {
+        description.push_str(" (interruptible)");
+    }
+
+    Ok(description)
+}
+
+/// The middleware instrumenting the code of an interruptible engine,
+/// see `Interruption`, and `wasmer.Function` for the host side.
+fn interruption_middleware() -> Arc<dyn wasmer_compiler::ModuleMiddleware> {
+    Arc::new(Interruption::new())
 }
 
 struct OpaqueCompilerInner {
@@ -231,7 +561,10 @@ pub struct JIT {}
 impl JIT {
     #[new]
     fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<(Self, Universal)> {
-        Ok((Self {}, Universal::raw_new(compiler, target)?))
+        Ok((
+            Self {},
+            Universal::raw_new(compiler, target, None, false, false)?,
+        ))
     }
 }
 
@@ -244,6 +577,9 @@ pub struct Native {}
 impl Native {
     #[new]
     fn new(compiler: Option<&PyAny>, target: Option<&Target>) -> PyResult<(Self, Dylib)> {
-        Ok((Self {}, Dylib::raw_new(compiler, target)?))
+        Ok((
+            Self {},
+            Dylib::raw_new(compiler, target, None, false, false)?,
+        ))
     }
 }
@@ -0,0 +1,138 @@
+use loupe::{MemoryUsage, MemoryUsageTracker};
+use std::{fmt, mem, sync::Mutex};
+use wasmer::{
+    wasmparser::{Operator, Type as WpType, TypeOrFuncType as WpTypeOrFuncType},
+    ExportIndex, FunctionMiddleware, GlobalInit, GlobalType, LocalFunctionIndex, MiddlewareError,
+    MiddlewareReaderState, ModuleMiddleware, Mutability, Type,
+};
+use wasmer_types::{GlobalIndex, ModuleInfo};
+
+/// The name of the global exported by the modules compiled by an
+/// interruptible engine. The host sets it to 1 to interrupt the
+/// running code. It is reserved: it is hidden from the exports seen
+/// from Python.
+pub const INTERRUPTED_GLOBAL: &str = "wasmer_python_interrupted";
+
+/// The middleware instrumenting the code of an interruptible engine.
+///
+/// Each module gets an `i32` global, exported as
+/// `INTERRUPTED_GLOBAL`, which the code checks at the start of each
+/// function and of each loop iteration: it traps if the global isn't
+/// zero. The code only reads the global, so the host can set it
+/// from another thread.
+///
+/// A single middleware is pushed in the compiler configuration of an
+/// engine, and is used for all the modules it compiles. The engine
+/// compiles one module at a time, so the index of the global is
+/// reset for each module by `transform_module_info`, before the
+/// functions of the module are compiled.
+pub(crate) struct Interruption {
+    /// The index of the global in the module being compiled.
+    global_index: Mutex<Option<GlobalIndex>>,
+}
+
+impl Interruption {
+    pub(crate) fn new() -> Self {
+        Self {
+            global_index: Mutex::new(None),
+        }
+    }
+}
+
+impl fmt::Debug for Interruption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Interruption")
+            .field("global_index", &self.global_index)
+            .finish()
+    }
+}
+
+impl MemoryUsage for Interruption {
+    fn size_of_val(&self, _tracker: &mut dyn MemoryUsageTracker) -> usize {
+        mem::size_of_val(self)
+    }
+}
+
+impl ModuleMiddleware for Interruption {
+    fn generate_function_middleware(&self, _: LocalFunctionIndex) -> Box<dyn FunctionMiddleware> {
+        let global_index = self
+            .global_index
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .expect("The module info must be transformed before its functions");
+
+        Box::new(FunctionInterruption {
+            global_index,
+            entered: false,
+        })
+    }
+
+    fn transform_module_info(&self, module_info: &mut ModuleInfo) {
+        let global_index = module_info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+
+        module_info
+            .global_initializers
+            .push(GlobalInit::I32Const(0));
+        module_info.exports.insert(
+            INTERRUPTED_GLOBAL.to_string(),
+            ExportIndex::Global(global_index),
+        );
+
+        *self
+            .global_index
+            .lock()
+            .unwrap_or_else(|error| error.into_inner()) = Some(global_index);
+    }
+}
+
+/// The function-level part of `Interruption`.
+#[derive(Debug)]
+struct FunctionInterruption {
+    global_index: GlobalIndex,
+
+    /// Whether the check at the start of the function has been
+    /// emitted.
+    entered: bool,
+}
+
+impl FunctionInterruption {
+    /// Emits `if (global.get $interrupted) { unreachable }`.
+    fn check(&self, state: &mut MiddlewareReaderState) {
+        state.extend(&[
+            Operator::GlobalGet {
+                global_index: self.global_index.as_u32(),
+            },
+            Operator::If {
+                ty: WpTypeOrFuncType::Type(WpType::EmptyBlockType),
+            },
+            Operator::Unreachable,
+            Operator::End,
+        ]);
+    }
+}
+
+impl FunctionMiddleware for FunctionInterruption {
+    fn feed<'a>(
+        &mut self,
+        operator: Operator<'a>,
+        state: &mut MiddlewareReaderState<'a>,
+    ) -> Result<(), MiddlewareError> {
+        if !self.entered {
+            self.entered = true;
+            self.check(state);
+        }
+
+        let is_loop = matches!(operator, Operator::Loop { .. });
+
+        state.push_operator(operator);
+
+        // Inside the loop, so that each iteration is checked.
+        if is_loop {
+            self.check(state);
+        }
+
+        Ok(())
+    }
+}
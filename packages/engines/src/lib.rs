@@ -1,7 +1,9 @@
 mod engines;
+mod interruption;
 mod target_lexicon;
 
 pub use crate::engines::{Dylib, OpaqueCompiler, Universal};
 // Deprecated engines.
 pub use crate::engines::{Native, JIT};
+pub use crate::interruption::INTERRUPTED_GLOBAL;
 pub use crate::target_lexicon::{CpuFeatures, Target, Triple};
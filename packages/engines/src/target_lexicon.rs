@@ -18,6 +18,9 @@ use std::str::FromStr;
 /// cpu_features.add('sse2')
 ///
 /// target = target.Target(triple, cpu_features)
+///
+/// assert str(target.triple) == 'x86_64-apple-darwin'
+/// assert repr(target) == "Target(triple='x86_64-apple-darwin', cpu_features=CpuFeatures(['sse2']))"
 /// ```
 #[pyclass]
 #[pyo3(text_signature = "(triple, cpu_features)")]
@@ -45,6 +48,53 @@ impl Target {
             ),
         }
     }
+
+    /// Build the target for the current host, i.e. the host triple
+    /// with all the CPU features of the host.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import target
+    ///
+    /// this_target = target.Target.host()
+    /// ```
+    #[staticmethod]
+    fn host() -> Self {
+        Self {
+            inner: wasmer_compiler::Target::new(
+                wasmer_compiler::Triple::host(),
+                wasmer_compiler::CpuFeature::for_host(),
+            ),
+        }
+    }
+
+    /// The triple of the target.
+    #[getter]
+    fn triple(&self) -> Triple {
+        Triple {
+            inner: self.inner.triple().clone(),
+        }
+    }
+
+    /// The CPU features of the target.
+    #[getter]
+    fn cpu_features(&self) -> CpuFeatures {
+        CpuFeatures {
+            inner: *self.inner.cpu_features(),
+        }
+    }
+}
+
+#[pyproto]
+impl PyObjectProtocol for Target {
+    fn __repr__(&self) -> String {
+        format!(
+            "Target(triple='{}', cpu_features={})",
+            self.inner.triple(),
+            self.cpu_features().__repr__()
+        )
+    }
 }
 
 /// A target “triple”.
@@ -243,6 +293,9 @@ impl PyObjectProtocol for Triple {
 ///
 /// cpu_features = target.CpuFeatures()
 /// cpu_features.add('sse2')
+/// cpu_features.add('avx')
+///
+/// assert repr(cpu_features) == "CpuFeatures(['sse2', 'avx'])"
 /// ```
 #[pyclass]
 #[pyo3(text_signature = "()")]
@@ -275,4 +328,62 @@ impl CpuFeatures {
 
         Ok(())
     }
+
+    /// Build a set of CPU features from a list of feature names. A
+    /// `ValueError` naming the first invalid feature is raised if any.
+    ///
+    /// ## Example
+    ///
+    /// ```py
+    /// from wasmer import target
+    ///
+    /// cpu_features = target.CpuFeatures.from_list(['sse2', 'avx', 'avx2'])
+    /// ```
+    #[staticmethod]
+    #[pyo3(text_signature = "(features)")]
+    fn from_list(features: Vec<&str>) -> PyResult<Self> {
+        let mut cpu_features = Self::new();
+
+        for feature in features {
+            cpu_features
+                .inner
+                .insert(wasmer_compiler::CpuFeature::from_str(feature).map_err(|_| {
+                    PyValueError::new_err(format!("The CPU feature `{}` is invalid", feature))
+                })?);
+        }
+
+        Ok(cpu_features)
+    }
+}
+
+impl CpuFeatures {
+    /// Returns the names of the CPU features of the set, as accepted
+    /// by `CpuFeatures.add`.
+    pub(crate) fn names(&self) -> Vec<String> {
+        self.inner.iter().map(cpu_feature_name).collect()
+    }
+}
+
+/// Returns the canonical name of a CPU feature, i.e. the name parsed
+/// by `wasmer_compiler::CpuFeature::from_str`.
+fn cpu_feature_name(feature: wasmer_compiler::CpuFeature) -> String {
+    feature.to_string()
+}
+
+#[pyproto]
+impl PyObjectProtocol for CpuFeatures {
+    fn __repr__(&self) -> String {
+        format!(
+            "CpuFeatures([{}])",
+            self.names()
+                .iter()
+                .map(|name| format!("'{}'", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+
+    fn __str__(&self) -> String {
+        self.__repr__()
+    }
 }
@@ -1,5 +1,5 @@
 import wasmer
-from wasmer import Instance, Module, Store, Function, FunctionType, Type, ImportObject
+from wasmer import Instance, Module, Store, Function, FunctionType, Type, ImportObject, Value
 import os
 import pytest
 
@@ -68,6 +68,14 @@ def test_type():
     assert type.params == [Type.I32, Type.I32]
     assert type.results == [Type.I32]
     assert str(type) == 'FunctionType(params: [I32, I32], results: [I32])'
+    assert repr(type) == 'FunctionType(params: [I32, I32], results: [I32], arity: (2, 1))'
+    assert type.arity == (2, 1)
+    assert not type.is_nullary
+
+def test_type_is_nullary():
+    assert FunctionType([], []).is_nullary
+    assert FunctionType([], []).arity == (0, 0)
+    assert not FunctionType([], [Type.I32]).is_nullary
 
 def test_basic_sum():
     assert value_with_type(instance().exports.sum(1, 2)) == (3, int)
@@ -101,6 +109,66 @@ def test_call_string():
 def test_call_void():
     assert instance().exports.void() == None
 
+def test_call_typed():
+    results = instance().exports.i64_i64.call_typed(7)
+
+    assert len(results) == 1
+    assert repr(results[0]) == 'I64(7)'
+
+def test_host_function_preserves_nan_payload():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "nan" (func $nan (result f32)))
+          (func (export "nan_bits") (result i32)
+            call $nan
+            i32.reinterpret_f32))
+        """
+    )
+
+    def nan():
+        return Value.f32_from_bits(0x7fa00001)
+
+    instance = Instance(
+        module,
+        {
+            "env": {
+                "nan": Function(store, nan, FunctionType([], [Type.F32]))
+            }
+        }
+    )
+
+    assert instance.exports.nan_bits() == 0x7fa00001
+
+def test_host_function_returns_value_with_wrong_type():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "f" (func $f (result f32)))
+          (func (export "run") (result f32)
+            call $f))
+        """
+    )
+
+    def f():
+        return Value.i32(42)
+
+    instance = Instance(
+        module,
+        {
+            "env": {
+                "f": Function(store, f, FunctionType([], [Type.F32]))
+            }
+        }
+    )
+
+    with pytest.raises(TypeError):
+        instance.exports.run()
+
 def test_early_exit():
     store = Store()
     module = Module(
@@ -190,3 +258,224 @@ def test_return_multiple_values_from_host_function():
     instance = Instance(module, import_object)
 
     assert instance.exports.test(41, 42) == (42, 41)
+
+def test_call_interrupted_by_signal():
+    import signal
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "tick" (func $tick))
+          (func (export "run")
+            (loop $continue
+              call $tick
+              br $continue)))
+        """
+    )
+
+    calls = []
+
+    def tick():
+        calls.append(None)
+
+        if len(calls) == 3:
+            signal.raise_signal(signal.SIGINT)
+
+    instance = Instance(module, {"env": {"tick": Function(store, tick, FunctionType([], []))}})
+
+    with pytest.raises(KeyboardInterrupt):
+        instance.exports.run()
+
+    assert len(calls) == 3
+
+@pytest.mark.skipif(os.name != 'posix', reason='`SIGINT` is sent with `kill`')
+def test_call_of_wasm_loop_interrupted_by_signal():
+    import subprocess
+
+    store = Store(interruptible=True)
+    module = Module(
+        store,
+        """
+        (module
+          (func (export "run")
+            (loop $continue
+              br $continue))
+          (func (export "answer") (result i32)
+            i32.const 42))
+        """
+    )
+    instance = Instance(module)
+
+    # The signal is sent by another process, like with Ctrl-C: a
+    # Python thread can't run while the call holds the GIL.
+    killer = subprocess.Popen(['sh', '-c', 'sleep 0.1 && kill -INT {}'.format(os.getpid())])
+
+    try:
+        with pytest.raises(KeyboardInterrupt):
+            instance.exports.run()
+    finally:
+        killer.wait()
+
+    # The instance can still be called once interrupted.
+    assert instance.exports.answer() == 42
+
+    with pytest.raises(ValueError):
+        Store(wasmer.engine.Universal(), interruptible=True)
+
+def test_from_export_chains_instances():
+    store = Store()
+    library = Instance(
+        Module(
+            store,
+            """
+            (module
+              (func (export "double") (param i32) (result i32)
+                local.get 0
+                i32.const 2
+                i32.mul))
+            """
+        )
+    )
+    program_module = Module(
+        store,
+        """
+        (module
+          (import "library" "double" (func $double (param i32) (result i32)))
+          (func (export "quadruple") (param i32) (result i32)
+            local.get 0
+            call $double
+            call $double))
+        """
+    )
+
+    double = Function.from_export(library.exports.double, store)
+
+    assert isinstance(double, Function)
+    assert double.type.params == library.exports.double.type.params
+    assert double.type.results == library.exports.double.type.results
+
+    program = Instance(program_module, {"library": {"double": double}})
+
+    assert program.exports.quadruple(3) == 12
+
+    # Exported functions can be registered as is too.
+    program = Instance(program_module, {"library": {"double": library.exports.double}})
+
+    assert program.exports.quadruple(5) == 20
+
+    with pytest.raises(ValueError):
+        Function.from_export(library.exports.double, Store())
+
+def test_from_export_keeps_store_and_trap_handler():
+    def fail():
+        raise ValueError('oops')
+
+    store = Store()
+    instance = Instance(
+        Module(
+            store,
+            """
+            (module
+              (import "env" "fail" (func $fail))
+              (func (export "run") call $fail))
+            """
+        ),
+        {"env": {"fail": Function(store, fail)}}
+    )
+
+    errors = []
+    instance.set_trap_handler(errors.append)
+
+    run = Function.from_export(instance.exports.run)
+
+    assert run.store is store
+
+    with pytest.raises(ValueError):
+        run()
+
+    assert len(errors) == 1
+
+def test_call_with_wrong_number_of_arguments():
+    sum = instance().exports.sum
+
+    with pytest.raises(TypeError) as context_manager:
+        sum(1, 2, 3)
+
+    assert str(context_manager.value) == 'The function expects 2 arguments, got 3'
+
+    with pytest.raises(TypeError) as context_manager:
+        sum(1)
+
+    assert str(context_manager.value) == 'The function expects 2 arguments, got 1'
+
+def test_call_with_argument_of_wrong_type():
+    with pytest.raises(TypeError) as context_manager:
+        instance().exports.sum(1, 'two')
+
+    assert str(context_manager.value).startswith('Argument 2 cannot be converted to `I32`: ')
+
+    with pytest.raises(OverflowError) as context_manager:
+        instance().exports.i64_i64(2 ** 64)
+
+    assert str(context_manager.value).startswith('Argument 1 cannot be converted to `I64`: ')
+
+def test_type_matches():
+    store = Store()
+    function = Function(store, lambda x, y: x + y, FunctionType([Type.I32, Type.I32], [Type.I32]))
+
+    assert function.type_matches(FunctionType([Type.I32, Type.I32], [Type.I32]))
+    assert not function.type_matches(FunctionType([Type.I32, Type.I64], [Type.I32]))
+    assert not function.type_matches(FunctionType([Type.I32, Type.I32], []))
+
+    module = Module(store, '(module (import "math" "sum" (func (param i32 i32) (result i32))))')
+
+    assert function.type_matches(module.imports[0].type)
+
+def test_is_host_function():
+    store = Store()
+    host_function = Function(store, lambda: None, FunctionType([], []))
+    instance = Instance(Module(store, '(module (func (export "f")))'))
+
+    assert host_function.is_host_function == True
+    assert instance.exports.f.is_host_function == False
+    assert Function.from_export(instance.exports.f).is_host_function == False
+
+def test_return_externref():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "get" (func $get (result externref)))
+          (func (export "get_object") (result externref)
+            call $get)
+          (func (export "get_null") (result externref)
+            ref.null extern)
+          (func $f)
+          (elem declare func $f)
+          (func (export "get_function") (result funcref)
+            ref.func $f))
+        """
+    )
+
+    class Object:
+        pass
+
+    host_object = Object()
+
+    import_object = ImportObject()
+    import_object.register(
+        "env",
+        {
+            "get": Function(store, lambda: host_object, FunctionType([], [Type.EXTERN_REF])),
+        }
+    )
+
+    exports = Instance(module, import_object).exports
+
+    assert exports.get_object() is host_object
+    assert exports.get_null() is None
+    assert isinstance(exports.get_function(), Function)
+    assert exports.get_function()() is None
@@ -38,6 +38,24 @@ def test_constructor():
     assert type.type == Type.I64
     assert type.mutable == False
 
+def test_typed_constructors():
+    store = Store()
+
+    assert Global.i32(store, 42).type.type == Type.I32
+    assert Global.i64(store, 42).type.type == Type.I64
+    assert Global.f32(store, 4.5).type.type == Type.F32
+    assert Global.f64(store, 4.5).type.type == Type.F64
+
+    global_ = Global.i32(store, 42)
+
+    assert global_.value == 42
+    assert global_.mutable == False
+
+    global_ = Global.f64(store, 4.5, mutable=True)
+
+    assert global_.value == 4.5
+    assert global_.mutable == True
+
 def test_constructor_mutable():
     store = Store()
     global_ = Global(store, Value.i32(42), mutable=True)
@@ -108,3 +126,49 @@ def test_global_read_write_constant():
     assert str(exception) == (
         'The global variable is not mutable, cannot set a new value'
     )
+
+def test_global_on_change():
+    exports = instance().exports
+    x = exports.x
+    changes = []
+
+    x.on_change(changes.append)
+    x.on_change(lambda value: changes.append(value * 10))
+
+    x.value = 3
+
+    assert changes == [3, 30]
+
+    # Writes from WebAssembly are not observed.
+    exports.increment_x()
+
+    assert changes == [3, 30]
+
+    with pytest.raises(ValueError):
+        x.on_change(42)
+
+def test_v128():
+    store = Store()
+
+    if not store.features.simd:
+        pytest.skip('SIMD is not enabled on the default store')
+
+    global_ = Global(store, Value.v128(2**100), mutable=True)
+
+    assert global_.value == 2**100
+    assert global_.type.type == Type.V128
+
+    global_.value = 2**128 - 1
+
+    assert global_.value == 2**128 - 1
+
+def test_v128_without_simd():
+    from wasmer import engine
+    from wasmer_compiler_cranelift import Compiler
+
+    store = Store(engine.Universal(Compiler, features={'simd': False}))
+
+    with pytest.raises(RuntimeError) as context_manager:
+        Global(store, Value.v128(42))
+
+    assert 'SIMD' in str(context_manager.value)
@@ -1,4 +1,4 @@
-from wasmer import ImportObject, Store, Module, Instance, Function, Memory, MemoryType, Global, Value
+from wasmer import ImportObject, Store, Module, Instance, Function, Memory, MemoryType, Global, Value, FunctionType, Type
 import pytest
 
 def test_constructor():
@@ -107,3 +107,76 @@ def test_import_global():
     assert instance.exports.read_g() == 153
     instance.exports.write_g(11)
     assert global_.value == 11
+
+def test_stubs_for():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "math" "sum" (func $sum (param i32 i32) (result i32)))
+          (import "env" "memory" (memory 1))
+          (import "env" "global" (global i32))
+          (func (export "add_one") (param i32) (result i32)
+            local.get 0
+            i32.const 1
+            call $sum)
+          (func (export "read_g") (result i32)
+            global.get 0))
+        """
+    )
+
+    import_object = ImportObject.stubs_for(store, module)
+
+    assert import_object.contains_namespace("math")
+    assert import_object.contains_namespace("env")
+
+    instance = Instance(module, import_object)
+
+    assert instance.exports.read_g() == 0
+
+    with pytest.raises(RuntimeError) as context_manager:
+        instance.exports.add_one(1)
+
+    assert "math.sum" in str(context_manager.value)
+
+def test_iterate():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+    import_object = ImportObject()
+    import_object.register(
+        "env",
+        {
+            "sum": Function(store, lambda x, y: x + y, FunctionType([Type.I32, Type.I32], [Type.I32])),
+            "memory": memory,
+        }
+    )
+
+    imports = sorted(
+        (namespace, name, type(extern))
+        for (namespace, name, extern) in import_object
+    )
+
+    assert [(namespace, name) for (namespace, name, _) in imports] == [("env", "memory"), ("env", "sum")]
+    assert [kind for (_, _, kind) in imports] == [Memory, Function]
+    assert list(ImportObject()) == []
+
+def test_clone():
+    store = Store()
+    sum = Function(store, lambda x, y: x + y, FunctionType([Type.I32, Type.I32], [Type.I32]))
+    base = ImportObject()
+    base.register("math", {"sum": sum})
+
+    import_object = base.clone()
+    import_object.register("env", {"memory": Memory(store, MemoryType(minimum=1))})
+
+    assert import_object.contains_namespace("math")
+    assert import_object.contains_namespace("env")
+    assert not base.contains_namespace("env")
+
+    base.register("other", {"sum": sum})
+
+    assert not import_object.contains_namespace("other")
+
+    module = Module(store, '(module (import "math" "sum" (func (param i32 i32) (result i32))) (import "env" "memory" (memory 1)))')
+    Instance(module, import_object)
@@ -13,6 +13,17 @@ def test_version():
 def test_core_version():
     assert isinstance(wasmer.__core_version__, str)
 
+def test_core_version_info():
+    (major, minor, patch) = wasmer.__core_version_info__
+
+    assert '{}.{}.{}'.format(major, minor, patch) in wasmer.__core_version__
+    assert wasmer.__core_version_info__ >= (2, 0, 0)
+
+def test_core_version_pre():
+    pre = wasmer.__core_version_pre__
+
+    assert pre is None or wasmer.__core_version__.endswith('-' + pre)
+
 def test_new():
     assert isinstance(Instance(Module(Store(), TEST_BYTES)), Instance)
 
@@ -98,9 +109,378 @@ def test_exports_iterable():
 
     assert [name for (name, _) in instance.exports] == ["func", "glob", "tab", "mem"]
 
+def test_exports_by_kind():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "f1"))
+          (global (export "glob") i32 (i32.const 7))
+          (func (export "f2"))
+          (table (export "tab") 0 funcref)
+          (memory (export "mem") 1))
+        """
+    )
+    exports = Instance(module).exports
+
+    functions = exports.functions()
+    assert list(functions.keys()) == ["f1", "f2"]
+    assert all(isinstance(function, Function) for function in functions.values())
+
+    assert list(exports.globals().keys()) == ["glob"]
+    assert isinstance(exports.globals()["glob"], Global)
+
+    assert list(exports.tables().keys()) == ["tab"]
+    assert isinstance(exports.tables()["tab"], Table)
+
+    assert list(exports.memories().keys()) == ["mem"]
+    assert isinstance(exports.memories()["mem"], Memory)
+
 def test_export_does_not_exist():
     with pytest.raises(LookupError) as context_manager:
         Instance(Module(Store(), TEST_BYTES)).exports.foo
 
     exception = context_manager.value
     assert str(exception) == 'Export `foo` does not exist.'
+
+def test_run_start_section():
+    store = Store()
+    module = Module(store, '(module (func $f) (start $f))')
+
+    assert module.has_start_function
+    assert Instance(module).run_start_section() == True
+
+    module = Module(store, '(module (func (export "_start")))')
+
+    assert not module.has_start_function
+    assert Instance(module).run_start_section() == False
+
+def test_set_trap_handler():
+    def fail():
+        raise ValueError('oops')
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "env" "fail" (func $fail))
+          (func (export "run") call $fail))
+        """
+    )
+    instance = Instance(module, {"env": {"fail": Function(store, fail)}})
+
+    errors = []
+    instance.set_trap_handler(errors.append)
+
+    with pytest.raises(ValueError):
+        instance.exports.run()
+
+    assert len(errors) == 1
+    assert str(errors[0]) == 'oops'
+
+    def replace(error):
+        raise LookupError('replaced')
+
+    instance.set_trap_handler(replace)
+
+    with pytest.raises(LookupError) as context_manager:
+        instance.exports.run()
+
+    assert str(context_manager.value) == 'replaced'
+
+    instance.set_trap_handler(None)
+
+    with pytest.raises(ValueError):
+        instance.exports.run()
+
+def test_instantiation_errors():
+    from wasmer import WasmerError, LinkError, TrapError
+
+    store = Store()
+
+    with pytest.raises(LinkError):
+        Instance(Module(store, '(module (import "env" "f" (func)))'))
+
+    with pytest.raises(TrapError) as context_manager:
+        Instance(Module(store, '(module (func $f unreachable) (start $f))'))
+
+    assert isinstance(context_manager.value, WasmerError)
+    assert isinstance(context_manager.value, RuntimeError)
+
+def test_trap_error():
+    from wasmer import TrapError
+
+    instance = Instance(Module(Store(), '(module (func (export "f") unreachable))'))
+
+    with pytest.raises(TrapError):
+        instance.exports.f()
+
+def test_exports_getitem():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func (export "a.b") (result i32) i32.const 1)
+          (func (export "x-y") (result i32) i32.const 2)
+          (func (export "sum") (result i32) i32.const 3))
+        """
+    )
+    exports = Instance(module).exports
+
+    assert exports["a.b"]() == 1
+    assert exports["x-y"]() == 2
+    assert exports["sum"]() == exports.sum()
+
+    with pytest.raises(KeyError):
+        exports["a"]
+
+    with pytest.raises(LookupError):
+        getattr(exports, "a")
+
+def test_repr():
+    store = Store()
+
+    assert repr(Instance(Module(store, '(module)'))) == 'Instance(exports=[])'
+    assert repr(Instance(Module(store, '(module (func (export "sum")) (memory (export "memory") 1))'))) == \
+        "Instance(exports=['sum', 'memory'])"
+
+    many_exports = ''.join('(func (export "f{}"))'.format(index) for index in range(12))
+    instance = Instance(Module(store, '(module {})'.format(many_exports)))
+
+    assert repr(instance) == \
+        "Instance(exports=['f0', 'f1', 'f2', 'f3', 'f4', 'f5', 'f6', 'f7', 'f8', 'f9', ...])"
+
+def test_initialize():
+    module = Module(
+        Store(),
+        """
+        (module
+          (global $ready (mut i32) (i32.const 0))
+          (func (export "_initialize")
+            i32.const 1
+            global.set $ready)
+          (func (export "is_ready") (result i32)
+            global.get $ready))
+        """
+    )
+    instance = Instance(module)
+
+    assert instance.exports.is_ready() == 0
+
+    instance.initialize()
+
+    assert instance.exports.is_ready() == 1
+
+    with pytest.raises(LookupError):
+        Instance(Module(Store(), '(module)')).initialize()
+
+@pytest.mark.skipif(os.name != 'posix', reason='`SIGINT` is sent with `kill`')
+def test_initialize_interrupted_by_signal():
+    import subprocess
+
+    instance = Instance(
+        Module(
+            Store(interruptible=True),
+            """
+            (module
+              (func (export "_initialize")
+                (loop $continue
+                  br $continue)))
+            """
+        )
+    )
+
+    # The signal is sent by another process, like with Ctrl-C: a
+    # Python thread can't run while the call holds the GIL.
+    killer = subprocess.Popen(['sh', '-c', 'sleep 0.1 && kill -INT {}'.format(os.getpid())])
+
+    try:
+        with pytest.raises(KeyboardInterrupt):
+            instance.initialize()
+    finally:
+        killer.wait()
+
+def test_max_memory_pages():
+    module = Module(
+        Store(),
+        """
+        (module
+          (memory (export "memory") 1 100)
+          (func (export "grow") (param i32) (result i32)
+            local.get 0
+            memory.grow))
+        """
+    )
+    instance = Instance(module, max_memory_pages=3)
+
+    assert instance.exports.grow(1) == 1
+
+    # Growing beyond the cap traps…
+    with pytest.raises(wasmer.TrapError) as context_manager:
+        instance.exports.grow(2)
+
+    assert '`max_memory_pages` (3 pages)' in str(context_manager.value)
+
+    # … but growing beyond the declared maximum still fails as usual.
+    assert instance.exports.grow(200) == -1
+
+    assert instance.exports.grow(1) == 2
+    assert instance.exports.memory.size == 3
+
+    with pytest.raises(RuntimeError):
+        instance.exports.memory.grow(1)
+
+    with pytest.raises(wasmer.LinkError):
+        Instance(module, max_memory_pages=0)
+
+    with pytest.raises(ValueError):
+        Instance(module, max_memory_pages=70000)
+
+ALLOCATOR_WAT = """
+(module
+  (memory (export "memory") 1)
+  (global $next (mut i32) (i32.const 1024))
+  (func (export "malloc") (param $length i32) (result i32)
+    global.get $next
+    global.get $next
+    local.get $length
+    i32.add
+    global.set $next)
+  (func (export "sum_bytes") (param $pointer i32) (param $length i32) (result i32)
+    (local $sum i32)
+    (block $done
+      (loop $next_byte
+        local.get $length
+        i32.eqz
+        br_if $done
+        local.get $sum
+        local.get $pointer
+        i32.load8_u
+        i32.add
+        local.set $sum
+        local.get $pointer
+        i32.const 1
+        i32.add
+        local.set $pointer
+        local.get $length
+        i32.const 1
+        i32.sub
+        local.set $length
+        br $next_byte))
+    local.get $sum))
+"""
+
+def test_pass_bytes():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+
+    (pointer, length) = instance.pass_bytes(b'\x01\x02\x03')
+
+    assert (pointer, length) == (1024, 3)
+    assert instance.exports.sum_bytes(pointer, length) == 6
+    assert instance.pass_bytes(bytearray(b'\x04')) == (1027, 1)
+
+    with pytest.raises(LookupError):
+        instance.pass_bytes(b'abc', allocator='alloc')
+
+    with pytest.raises(RuntimeError):
+        Instance(Module(Store(), '(module (func (export "malloc") (param i32) (result i32) i32.const 0))')).pass_bytes(b'abc')
+
+@pytest.mark.skipif(os.name != 'posix', reason='`SIGINT` is sent with `kill`')
+def test_pass_bytes_interrupted_by_signal():
+    import subprocess
+
+    instance = Instance(
+        Module(
+            Store(interruptible=True),
+            """
+            (module
+              (memory (export "memory") 1)
+              (func (export "malloc") (param i32) (result i32)
+                (loop $continue
+                  br $continue)
+                i32.const 0))
+            """
+        )
+    )
+
+    # The signal is sent by another process, like with Ctrl-C: a
+    # Python thread can't run while the call holds the GIL.
+    killer = subprocess.Popen(['sh', '-c', 'sleep 0.1 && kill -INT {}'.format(os.getpid())])
+
+    try:
+        with pytest.raises(KeyboardInterrupt):
+            instance.pass_bytes(b'abc')
+    finally:
+        killer.wait()
+
+def test_read_bytes():
+    instance = Instance(Module(Store(), ALLOCATOR_WAT))
+    (pointer, length) = instance.pass_bytes(b'Hello, World!')
+
+    assert instance.read_bytes(pointer, length) == b'Hello, World!'
+    assert instance.read_bytes(pointer + 7, 5) == b'World'
+    assert instance.read_bytes(0, 0) == b''
+
+    with pytest.raises(ValueError):
+        instance.read_bytes(65536 - 2, 3)
+
+    with pytest.raises(RuntimeError):
+        Instance(Module(Store(), '(module)')).read_bytes(0, 1)
+
+def test_memories_tables_globals():
+    instance = Instance(
+        Module(
+            Store(),
+            """
+            (module
+              (memory (export "mem") 1)
+              (table (export "tab") 2 funcref)
+              (global (export "g1") i32 (i32.const 1))
+              (global (export "g2") (mut i64) (i64.const 2))
+              (func (export "f")))
+            """
+        )
+    )
+
+    assert [type(memory) for memory in instance.memories] == [Memory]
+    assert instance.memories[0].size == 1
+    assert [table.size for table in instance.tables] == [2]
+    assert [global_.value for global_ in instance.globals] == [1, 2]
+    assert Instance(Module(Store(), '(module)')).memories == []
+
+def test_call():
+    module = Module(
+        Store(),
+        """
+        (module
+          (memory (export "memory") 1)
+          (func (export "sum") (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.add)
+          (func (export "product") (param i32 i32) (result i32)
+            local.get 0
+            local.get 1
+            i32.mul))
+        """
+    )
+    instance = Instance(module)
+
+    assert instance.call('sum', 1, 2) == 3
+    assert instance.call('product', 2, 3) == 6
+
+    with pytest.raises(AttributeError) as context_manager:
+        instance.call('sun', 1, 2)
+
+    assert str(context_manager.value) == 'The instance has no exported function `sun`; did you mean `sum`?'
+
+    with pytest.raises(AttributeError) as context_manager:
+        instance.call('memory')
+
+    assert str(context_manager.value).startswith('The export `memory` is not a function')
+
+    with pytest.raises(AttributeError) as context_manager:
+        instance.call('foo')
+
+    assert str(context_manager.value) == 'The instance has no exported function `foo`; the exported functions are: `sum`, `product`'
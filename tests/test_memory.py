@@ -18,6 +18,48 @@ def test_constructor():
 
     assert memory.size == 3
 
+def test_constructor_with_data():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1), data=b'Wasmer')
+
+    assert memory.size == 1
+    assert bytearray(memory.buffer)[0:6] == b'Wasmer'
+
+def test_constructor_with_data_buffer():
+    store = Store()
+
+    memory = Memory(store, MemoryType(minimum=1), data=bytearray(b'Wasmer'))
+    assert bytearray(memory.buffer)[0:6] == b'Wasmer'
+
+    memory = Memory(store, MemoryType(minimum=1), data=memoryview(b'Wasmer')[1:])
+    assert bytearray(memory.buffer)[0:5] == b'asmer'
+
+def test_constructor_with_data_grows():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1), data=bytes(65537))
+
+    assert memory.size == 2
+
+def test_constructor_with_data_exceeding_maximum():
+    store = Store()
+
+    with pytest.raises(ValueError):
+        Memory(store, MemoryType(minimum=1, maximum=1), data=bytes(65537))
+
+def test_constructor_with_data_exceeding_wasm_limit():
+    import mmap
+
+    store = Store()
+
+    # Anonymous mappings are lazily allocated: the 4GiB aren't used.
+    data = mmap.mmap(-1, 65536 * 65536 + 1)
+
+    try:
+        with pytest.raises(ValueError):
+            Memory(store, MemoryType(minimum=1), data=data)
+    finally:
+        data.close()
+
 def test_export():
     assert isinstance(instance().exports.memory, Memory)
 
@@ -36,6 +78,12 @@ def test_size():
 def test_data_size():
     assert instance().exports.memory.data_size == 1114112
 
+def test_max_data_size():
+    store = Store()
+
+    assert Memory(store, MemoryType(minimum=1, maximum=3)).max_data_size == 196608
+    assert Memory(store, MemoryType(minimum=1)).max_data_size == None
+
 def test_memory_buffer():
     memory = instance().exports.memory.buffer
     assert isinstance(memory, Buffer)
@@ -244,6 +292,32 @@ def test_memory_grow_too_much():
         'The memory could not grow: current size 17 pages, requested increase: 100000 pages'
     )
 
+def test_memory_ensure_size():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=3, maximum=10))
+
+    assert memory.ensure_size(5) == 5
+    assert memory.ensure_size(2) == 5
+    assert memory.size == 5
+
+    with pytest.raises(RuntimeError):
+        memory.ensure_size(11)
+
+def test_memory_grow_bytes():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1, maximum=3))
+
+    assert memory.grow_bytes(1) == 65536
+    assert memory.size == 2
+    assert memory.grow_bytes(65536) == 131072
+    assert memory.size == 3
+
+    with pytest.raises(ValueError):
+        memory.grow_bytes(0)
+
+    with pytest.raises(RuntimeError):
+        memory.grow_bytes(1)
+
 def test_memory_buffer_memoryview():
     memory = instance().exports.memory
 
@@ -329,6 +403,101 @@ def test_memory_buffer_supports_keeps_object_alive():
     for i in range(len(view) // 1024):
         view[i * 1024:(i + 1) * 1024] = val
 
+def test_memory_as_numpy():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+
+    int32 = memory.int32_view()
+    int32[1] = 42
+    int32[2] = -7
+
+    assert list(memory.as_numpy('int32', offset=4, count=2)) == [42, -7]
+    assert len(memory.as_numpy()) == 65536
+    assert len(memory.as_numpy('float64')) == 8192
+
+def test_memory_as_numpy_out_of_bound():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+
+    with pytest.raises(IndexError):
+        memory.as_numpy('uint32', offset=65532, count=2)
+
+def test_memory_as_numpy_invalid_dtype():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+
+    with pytest.raises(ValueError):
+        memory.as_numpy('complex128')
+
+def test_memory_read_cstring():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1), data=b'Wasmer\0\xffrocks\0')
+
+    assert memory.read_cstring(0) == 'Wasmer'
+    assert memory.read_cstring(0, max_length=4) == 'Wasm'
+    assert memory.read_cstring(6) == ''
+
+    with pytest.raises(UnicodeDecodeError):
+        memory.read_cstring(7)
+
+    assert memory.read_cstring(7, errors='replace') == '\ufffdrocks'
+    assert memory.read_cstring(7, errors='ignore') == 'rocks'
+
+    with pytest.raises(IndexError):
+        memory.read_cstring(65537)
+
+def test_memory_view():
+    from wasmer import Type
+
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+
+    assert isinstance(memory.view('u8'), Uint8Array)
+    assert isinstance(memory.view('i16'), Int16Array)
+    assert isinstance(memory.view('u64', offset=8), Uint64Array)
+    assert isinstance(memory.view(Type.I32), Int32Array)
+    assert isinstance(memory.view(Type.F64), Float64Array)
+
+    memory.view('i32')[1] = 42
+    assert memory.view(Type.I32, offset=1)[0] == 42
+
+    with pytest.raises(ValueError):
+        memory.view('u128')
+
+    with pytest.raises(ValueError):
+        memory.view(Type.V128)
+
+def test_memory_page_size():
+    assert Memory.PAGE_SIZE == 65536
+
+def test_memory_pages_for_bytes():
+    assert Memory.pages_for_bytes(0) == 0
+    assert Memory.pages_for_bytes(1) == 1
+    assert Memory.pages_for_bytes(Memory.PAGE_SIZE - 1) == 1
+    assert Memory.pages_for_bytes(Memory.PAGE_SIZE) == 1
+    assert Memory.pages_for_bytes(Memory.PAGE_SIZE + 1) == 2
+    assert Memory.pages_for_bytes(3 * Memory.PAGE_SIZE) == 3
+    assert Memory.pages_for_bytes(3 * Memory.PAGE_SIZE + 1) == 4
+
+def test_memory_uint64_view_large_values():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+    uint64 = memory.uint64_view()
+
+    uint64[0] = 2**63 + 1
+    uint64[1:3] = [2**64 - 1, 2**63]
+
+    assert uint64[0] == 2**63 + 1
+    assert uint64[1:3] == [2**64 - 1, 2**63]
+    assert memory.int64_view()[0] == -2**63 + 1
+    assert memory.int64_view()[1] == -1
+
+    with pytest.raises(OverflowError):
+        uint64[0] = 2**64
+
+    with pytest.raises(OverflowError):
+        uint64[0] = -1
+
 def test_memory_views_length():
     store = Store()
     memory_type = MemoryType(minimum=1, maximum=1)
@@ -347,3 +516,84 @@ def test_memory_views_length():
     assert len(memory.int64_view()) == PAGE_SIZE/8
     assert len(memory.float32_view()) == PAGE_SIZE/4
     assert len(memory.float64_view()) == PAGE_SIZE/8
+
+def test_memory_read_into():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1), data=b'Hello, World!')
+    buffer = bytearray(5)
+
+    assert memory.read_into(7, buffer) == 5
+    assert buffer == b'World'
+
+    assert memory.read_into(0, memoryview(buffer)[:2]) == 2
+    assert buffer == b'Herld'
+
+    with pytest.raises(ValueError):
+        memory.read_into(65535, buffer)
+
+    with pytest.raises(TypeError):
+        memory.read_into(0, b'immutable')
+
+def test_memory_copy_from_bytes():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1, maximum=3))
+
+    assert memory.copy_from_bytes(7, b'World') == 5
+    assert bytearray(memory.buffer)[7:12] == b'World'
+
+    with pytest.raises(ValueError):
+        memory.copy_from_bytes(65535, b'abc')
+
+    assert memory.size == 1
+
+    assert memory.copy_from_bytes(65535, bytearray(b'abc'), grow=True) == 3
+    assert memory.size == 2
+    assert bytearray(memory.buffer)[65535:65538] == b'abc'
+
+    with pytest.raises(ValueError):
+        memory.copy_from_bytes(0, bytes(3 * 65536 + 1), grow=True)
+
+    assert memory.size == 2
+
+def test_memory_find():
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1), data=b'Hello, World! Hello!')
+
+    assert memory.find(b'Hello') == 0
+    assert memory.find(b'Hello', start=1) == 14
+    assert memory.find(b'World', end=11) == -1
+    assert memory.find(b'World', end=12) == 7
+    assert memory.find(b'nope') == -1
+    assert memory.find(b'') == 0
+    assert memory.find(b'Hello', start=100000) == -1
+    assert memory.find(b'', start=100000) == -1
+    assert memory.find(b'', start=3) == 3
+    assert memory.find(b'', start=3, end=2) == -1
+    assert memory.find(b'\x00' * 4, start=20) == 20
+
+def test_memory_write_values():
+    from wasmer import Type
+
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+
+    assert memory.write_values(8, [1, -2, 3], 'i32') == 12
+    assert memory.int32_view(offset=2)[0:3] == [1, -2, 3]
+
+    assert memory.write_values(16, [1.5, 2.5], Type.F64) == 16
+    assert memory.float64_view(offset=2)[0:2] == [1.5, 2.5]
+
+    assert memory.write_values(0, [255, 0], 'u8') == 2
+    assert memory.uint8_view()[0:2] == [255, 0]
+
+    with pytest.raises(ValueError):
+        memory.write_values(0, [1, 256], 'u8')
+
+    # Nothing has been written when a value is invalid.
+    assert memory.uint8_view()[0:2] == [255, 0]
+
+    with pytest.raises(IndexError):
+        memory.write_values(65532, [1, 2], 'i32')
+
+    with pytest.raises(ValueError):
+        memory.write_values(0, [1], 'i128')
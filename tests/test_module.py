@@ -14,6 +14,30 @@ def test_validate():
 def test_validate_invalid():
     assert not Module.validate(Store(), INVALID_TEST_BYTES)
 
+def test_validate_detailed():
+    store = Store()
+
+    assert Module.validate_detailed(store, TEST_BYTES) == None
+
+    error = Module.validate_detailed(store, INVALID_TEST_BYTES)
+
+    assert isinstance(error, str)
+    assert error != ''
+
+    assert Module.validate_detailed(store, '(module)').startswith('The module must be given as `bytes`')
+
+def test_looks_like_wasm():
+    assert Module.looks_like_wasm(TEST_BYTES)
+    assert not Module.looks_like_wasm(b'\x00asm')
+    assert not Module.looks_like_wasm('(module)')
+    assert not Module.looks_like_wasm(42)
+
+def test_looks_like_wat():
+    assert Module.looks_like_wat('(module)')
+    assert not Module.looks_like_wat('(module')
+    assert not Module.looks_like_wat(TEST_BYTES)
+    assert not Module.looks_like_wat(42)
+
 def test_compile_bytes():
     assert isinstance(Module(Store(), TEST_BYTES), Module)
 
@@ -24,17 +48,48 @@ def test_failed_to_compile():
     with pytest.raises(RuntimeError) as context_manager:
         Module(Store(), INVALID_TEST_BYTES)
 
+def test_failed_to_compile_error_hierarchy():
+    from wasmer import CompileError, WasmerError
+
+    assert issubclass(CompileError, WasmerError)
+    assert issubclass(CompileError, RuntimeError)
+
+    with pytest.raises(CompileError):
+        Module(Store(), INVALID_TEST_BYTES)
+
+def test_component_is_rejected():
+    # The preamble of a component: the `\0asm` magic, a version, and
+    # the layer 1.
+    component_bytes = b'\x00asm\x0d\x00\x01\x00'
+
+    with pytest.raises(ValueError) as context_manager:
+        Module(Store(), component_bytes)
+
+    assert str(context_manager.value) == 'This is a WebAssembly component, not a core module; component support is not available.'
+
 def test_name_some():
     assert Module(Store(), '(module $moduleName)').name == 'moduleName'
 
 def test_name_none():
     assert Module(Store(), '(module)').name == None
 
+def test_read_name():
+    assert Module.read_name(wasmer.wat2wasm('(module $moduleName)')) == 'moduleName'
+    assert Module.read_name(wasmer.wat2wasm('(module)')) == None
+    assert Module.read_name(b'\x00asm\x01\x00\x00\x00\x00\xff') == None
+    assert Module.read_name(b'garbage') == None
+    assert Module.read_name('(module $moduleName)') == None
+
 def test_name_set():
     module = Module(Store(), '(module)')
     module.name = 'hello'
     assert module.name == 'hello'
 
+def test_name_unset():
+    module = Module(Store(), '(module $moduleName)')
+    module.name = None
+    assert module.name == None
+
 def test_exports():
     exports = Module(
         Store(),
@@ -111,12 +166,151 @@ def test_imports():
     assert imports[3].type.maximum == 4
     assert imports[3].type.shared == False
 
+def test_exports_and_imports_by_kind():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "ns" "f" (func))
+          (import "ns" "g" (global f32))
+          (import "ns" "t" (table 1 anyfunc))
+          (import "ns" "m" (memory 1))
+          (func (export "function"))
+          (func (export "other_function"))
+          (global (export "global") i32 (i32.const 7))
+          (export "memory" (memory 0)))
+        """
+    )
+
+    assert [export.name for export in module.exported_functions] == ["function", "other_function"]
+    assert [export.name for export in module.exported_globals] == ["global"]
+    assert [export.name for export in module.exported_memories] == ["memory"]
+    assert module.exported_tables == []
+
+    assert [import_.name for import_ in module.imported_functions] == ["f"]
+    assert [import_.name for import_ in module.imported_globals] == ["g"]
+    assert [import_.name for import_ in module.imported_tables] == ["t"]
+    assert [import_.name for import_ in module.imported_memories] == ["m"]
+    assert isinstance(module.imported_memories[0], ImportType)
+
+def test_import_export_count():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "ns" "function" (func))
+          (import "ns" "memory" (memory 3 4))
+          (func (export "function")))
+        """
+    )
+
+    assert module.import_count == 2
+    assert module.export_count == 1
+    assert Module(Store(), '(module)').import_count == 0
+
+def test_required_features():
+    store = Store()
+
+    features = Module(store, '(module)').required_features()
+
+    assert set(features.keys()) == {'bulk_memory', 'exceptions', 'memory64', 'module_linking', 'multi_memory', 'multi_value', 'reference_types', 'simd', 'tail_call', 'threads'}
+    assert not any(features.values())
+
+    features = Module(store, '(module (func (param v128)))').required_features()
+
+    assert features['simd']
+    assert not features['multi_value']
+
+    features = Module(store, '(module (func (result i32 i64) i32.const 1 i64.const 2))').required_features()
+
+    assert features['multi_value']
+    assert not features['simd']
+
+def test_required_features_of_deserialized_module():
+    store = Store()
+    module = Module.deserialize(store, Module(store, '(module)').serialize())
+
+    with pytest.raises(RuntimeError):
+        module.required_features()
+
+def test_import_export_types_repr():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "env" "log" (func (param i32)))
+          (memory (export "memory") 1))
+        """
+    )
+
+    assert repr(module.imports[0]) == "ImportType(module='env', name='log', type=FunctionType(params: [I32], results: []))"
+    assert repr(module.exports[0]) == "ExportType(name='memory', type=MemoryType(minimum: 1, maximum: None, shared: false))"
+
+def test_import_export_types_eq():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "env" "log" (func (param i32)))
+          (memory (export "memory") 1))
+        """
+    )
+
+    assert module.imports[0] == ImportType("env", "log", FunctionType([Type.I32], []))
+    assert module.imports[0] != ImportType("env", "log", FunctionType([Type.I64], []))
+    assert module.exports[0] == ExportType("memory", MemoryType(minimum=1))
+    assert module.exports[0] != ExportType("memory", MemoryType(minimum=2))
+
+def test_import_export_types_hash():
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "env" "log" (func (param i32)))
+          (memory (export "memory") 1))
+        """
+    )
+
+    assert hash(module.imports[0]) == hash(ImportType("env", "log", FunctionType([Type.I32], [])))
+    assert hash(module.exports[0]) == hash(ExportType("memory", MemoryType(minimum=1)))
+    assert len({module.imports[0], module.imports[0], module.exports[0]}) == 2
+
 def test_custom_section():
     module = Module(Store(), open(here + '/custom_sections.wasm', 'rb').read())
     assert module.custom_sections('easter_egg') == [b'Wasmer']
     assert module.custom_sections('hello') == [b'World!']
     assert module.custom_sections('foo') == []
 
+def test_strip():
+    module = Module(Store(), open(here + '/custom_sections.wasm', 'rb').read())
+
+    stripped_module = module.strip()
+    assert stripped_module.custom_sections('easter_egg') == []
+    assert stripped_module.custom_sections('hello') == []
+
+    stripped_module = module.strip(keep=['hello'])
+    assert stripped_module.custom_sections('easter_egg') == []
+    assert stripped_module.custom_sections('hello') == [b'World!']
+
+    assert module.custom_sections('easter_egg') == [b'Wasmer']
+
+def test_strip_still_runs():
+    from wasmer import Instance
+
+    module = Module(Store(), '(module $sum (func (export "sum") (param i32 i32) (result i32) local.get 0 local.get 1 i32.add))')
+    assert module.name == 'sum'
+
+    stripped_module = module.strip()
+    assert stripped_module.name == None
+    assert Instance(stripped_module).exports.sum(1, 2) == 3
+
+def test_strip_deserialized_module():
+    store = Store()
+    module = Module.deserialize(store, Module(store, '(module)').serialize())
+
+    with pytest.raises(RuntimeError):
+        module.strip()
+
 def test_serialize():
     assert type(Module(Store(), "(module)").serialize()) == bytes
 
@@ -140,3 +334,143 @@ def test_deserialize():
     assert isinstance(exports[0].type, FunctionType)
     assert exports[0].type.params == [Type.I32, Type.I64]
     assert exports[0].type.results == []
+
+def test_deserialize_untrusted():
+    store = Store()
+    serialized_module = Module(store, '(module (func (export "function")))').serialize()
+
+    module = Module.deserialize(store, serialized_module, trusted=False)
+
+    assert module.export_count == 1
+
+    with pytest.raises(ValueError):
+        Module.deserialize(store, b'\x00asm\x01\x00\x00\x00', trusted=False)
+
+def test_engine_load_bytes():
+    serialized_module = Module(
+        Store(),
+        """
+        (module
+          (func (export "function") (param i32 i64)))
+        """
+    ).serialize()
+    module = wasmer.engine.load(Store(wasmer.engine.Universal()), serialized_module)
+
+    assert module.exports[0].name == "function"
+
+def test_engine_load_path(tmp_path):
+    path = tmp_path / "module.serialized"
+    path.write_bytes(Module(Store(), "(module)").serialize())
+
+    assert isinstance(wasmer.engine.load(Store(wasmer.engine.Universal()), path), Module)
+    assert isinstance(wasmer.engine.load(Store(wasmer.engine.Universal()), str(path)), Module)
+
+def test_serialized_format_version():
+    store = Store()
+    serialized_module = Module(store, "(module)").serialize()
+
+    assert Module.serialized_format_version(serialized_module) == wasmer.__core_version__
+    assert Module.serialized_format_version(b"\x00asm\x01\x00\x00\x00") == None
+
+    tampered_module = serialized_module.replace(
+        wasmer.__core_version__.encode(),
+        b"0.0.0",
+        1
+    )
+
+    assert Module.serialized_format_version(tampered_module) == "0.0.0"
+
+    with pytest.raises(ValueError) as context_manager:
+        Module.deserialize(store, tampered_module)
+
+    assert str(context_manager.value) == (
+        "The module has been serialized with wasmer 0.0.0, cannot load it under wasmer " + wasmer.__core_version__
+    )
+
+    with pytest.raises(ValueError):
+        wasmer.engine.load(Store(wasmer.engine.Universal()), tampered_module)
+
+def test_engine_load_requires_headless_engine():
+    with pytest.raises(ValueError):
+        wasmer.engine.load(Store(), Module(Store(), "(module)").serialize())
+
+def test_write_dylib(tmp_path):
+    from wasmer_compiler_cranelift import Compiler
+
+    module = Module(Store(wasmer.engine.Dylib(Compiler)), "(module)")
+    path = tmp_path / "module.so"
+    module.write_dylib(path)
+
+    # `serialize` prepends a version tag to the shared object.
+    assert module.serialize().endswith(path.read_bytes())
+
+def test_write_dylib_requires_dylib_engine(tmp_path):
+    with pytest.raises(RuntimeError):
+        Module(Store(), "(module)").write_dylib(tmp_path / "module.so")
+
+def test_equality_and_hash():
+    wasm_bytes = wasmer.wat2wasm('(module (func (export "f")))')
+    module = Module(Store(), wasm_bytes)
+    same_module = Module(Store(), '(module (func (export "f")))')
+    other_module = Module(Store(), '(module (func (export "g")))')
+
+    assert module == same_module
+    assert hash(module) == hash(same_module)
+    assert module != other_module
+    assert module != wasm_bytes
+    assert len({module, same_module, other_module}) == 2
+
+    # Different store configuration.
+    from wasmer_compiler_cranelift import Compiler
+
+    assert module != Module(Store(wasmer.engine.Dylib(Compiler)), wasm_bytes)
+
+    # Deserialized modules are compared by artifact.
+    store = Store()
+    serialized_module = Module(store, wasm_bytes).serialize()
+    assert Module.deserialize(store, serialized_module) == Module.deserialize(store, serialized_module)
+
+def test_compile_async():
+    import asyncio
+
+    async def compile():
+        store = Store()
+        module = await Module.compile_async(store, TEST_BYTES)
+
+        assert isinstance(module, Module)
+        assert module == Module(store, TEST_BYTES)
+
+        with pytest.raises(wasmer.CompileError):
+            await Module.compile_async(store, b'\x00asm\x01\x00\x00\x00\xff')
+
+    asyncio.run(compile())
+
+    # There is no running event loop.
+    with pytest.raises(RuntimeError):
+        Module.compile_async(Store(), TEST_BYTES)
+
+def test_iter_exports_and_imports():
+    module = Module(Store(), TEST_BYTES)
+    exports = module.iter_exports()
+
+    assert iter(exports) is exports
+    assert [(export.name, str(export.type)) for export in exports] == \
+        [(export.name, str(export.type)) for export in module.exports]
+
+    module = Module(
+        Store(),
+        """
+        (module
+          (import "ns" "function" (func (param i32)))
+          (import "ns" "memory" (memory 1))
+          (import "ns" "global" (global i64))
+          (import "ns" "table" (table 1 funcref)))
+        """
+    )
+    imports = module.iter_imports()
+    first = next(imports)
+
+    assert (first.module, first.name) == ('ns', 'function')
+    assert isinstance(first.type, FunctionType)
+    assert [type(import_.type) for import_ in imports] == [MemoryType, GlobalType, TableType]
+    assert list(Module(Store(), '(module)').iter_imports()) == []
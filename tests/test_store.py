@@ -1,4 +1,5 @@
 from wasmer import engine, Store, Module, Instance
+import wasmer
 import itertools
 import os
 import platform
@@ -73,3 +74,262 @@ def test_store_deprecated_native():
 #            instance = Instance(module)
 #
 #            assert instance.exports.sum(1, 2)
+
+def test_store_engine():
+    universal = engine.Universal()
+    store = Store(universal)
+
+    assert store.engine is universal
+    assert isinstance(Store().engine, engine.Universal)
+    assert isinstance(Store(engine.Dylib()).engine, engine.Dylib)
+
+def test_store_engine_shared():
+    store = Store()
+    other_store = Store(store.engine)
+
+    assert other_store.engine is store.engine
+    assert other_store.compiler_name == store.compiler_name
+
+def test_store_id():
+    from wasmer import Memory, MemoryType, Global, Value
+
+    store = Store()
+    other_store = Store()
+
+    assert store.id != other_store.id
+    assert Store(store.engine).id != store.id
+    assert Memory(store, MemoryType(minimum=1)).store_id == store.id
+    assert Global(other_store, Value.i32(42)).store_id == other_store.id
+    assert Memory(Store(store.engine), MemoryType(minimum=1)).store_id != store.id
+
+def test_extern_store():
+    from wasmer import Instance, Memory, MemoryType, Global, Value
+
+    store = Store()
+    memory = Memory(store, MemoryType(minimum=1))
+
+    assert memory.store is store
+    assert Global(memory.store, Value.i32(42)).store is store
+
+    module = Module(store, TEST_BYTES)
+    instance = Instance(module)
+
+    assert instance.exports.memory.store is store
+    assert instance.exports.sum.store is store
+
+def test_store_cache_dir(tmp_path):
+    from wasmer_compiler_cranelift import Compiler
+
+    store = Store(cache_dir=str(tmp_path))
+    module = Module(store, TEST_BYTES)
+
+    assert len(list(tmp_path.iterdir())) == 1
+
+    cached_module = Module(store, TEST_BYTES)
+
+    assert len(list(tmp_path.iterdir())) == 1
+    assert Instance(cached_module).exports.sum(1, 2) == 3
+
+    # A different compiler configuration must not hit the cache.
+    Module(Store(engine.Universal(Compiler, deterministic=True), deterministic=True, cache_dir=tmp_path), TEST_BYTES)
+
+    assert len(list(tmp_path.iterdir())) == 2
+
+    store.clear_cache()
+
+    assert len(list(tmp_path.iterdir())) == 0
+
+def test_store_cache_dir_checks_entries(tmp_path):
+    store = Store(cache_dir=tmp_path)
+
+    Module(store, '(module (func (export "f")))')
+    (f_path,) = tmp_path.iterdir()
+
+    Module(store, '(module (func (export "g")))')
+    (g_path,) = [path for path in tmp_path.iterdir() if path != f_path]
+
+    # An entry moved to the key of another module is not loaded…
+    g_path.write_bytes(f_path.read_bytes())
+
+    assert [export.name for export in Module(store, '(module (func (export "g")))').exports] == ['g']
+
+    # … nor a file that hasn't been written by the cache.
+    g_path.write_bytes(b'garbage')
+
+    assert [export.name for export in Module(store, '(module (func (export "g")))').exports] == ['g']
+
+def test_store_cache_dir_features(tmp_path):
+    from wasmer_compiler_cranelift import Compiler
+
+    simd_store = Store(engine.Universal(Compiler, features={'simd': True}), cache_dir=tmp_path)
+    no_simd_store = Store(engine.Universal(Compiler, features={'simd': False}), cache_dir=tmp_path)
+
+    simd_module = Module(simd_store, TEST_BYTES)
+
+    # Stores differing only by their features must not share the
+    # cached artifacts…
+    no_simd_module = Module(no_simd_store, TEST_BYTES)
+
+    assert len(list(tmp_path.iterdir())) == 2
+
+    # … nor be considered equal.
+    assert simd_module != no_simd_module
+
+    # A module requiring SIMD must still be validated by the store
+    # without SIMD, even if it has been cached by the other store.
+    simd_bytes = wasmer.wat2wasm('(module (func (drop (v128.const i32x4 0 0 0 0))))')
+    Module(simd_store, simd_bytes)
+
+    with pytest.raises(wasmer.CompileError):
+        Module(no_simd_store, simd_bytes)
+
+def test_store_compile():
+    store = Store()
+    wat = '(module (func (export "answer") (result i32) i32.const 42))'
+
+    assert isinstance(store.compile(wat), Module)
+
+    wasm_bytes = wasmer.wat2wasm(wat)
+
+    for source in (wasm_bytes, bytearray(wasm_bytes), memoryview(wasm_bytes)):
+        module = store.compile(source)
+
+        assert Instance(module).exports.answer() == 42
+
+    with pytest.raises(TypeError):
+        store.compile(42)
+
+def test_store_features():
+    features = Store().features
+
+    assert isinstance(features, wasmer.Features)
+    assert features.bulk_memory == True
+    assert features.threads == False
+    assert repr(features).startswith("Features(threads=False, ")
+
+    assert Store(engine.Universal()).features.multi_value == True
+
+def test_features_constructor():
+    features = wasmer.Features()
+
+    assert features.threads == False
+    assert features.reference_types == True
+    assert features.simd == True
+    assert features.bulk_memory == True
+    assert features.multi_value == True
+    assert features.memory64 == False
+
+    features = wasmer.Features(threads=True, simd=False)
+
+    assert features.threads == True
+    assert features.simd == False
+
+    features.simd = True
+    features.tail_call = True
+
+    assert features.simd == True
+    assert features.tail_call == True
+
+    with pytest.raises(TypeError):
+        features.simd = 'yes'
+
+def test_engine_features():
+    from wasmer_compiler_cranelift import Compiler
+
+    store = Store(engine.Universal(Compiler, features={'simd': False, 'threads': True}))
+
+    assert store.features.simd == False
+    assert store.features.threads == True
+    assert store.features.bulk_memory == True
+
+    store = Store(engine.Dylib(Compiler, features=store.features))
+
+    assert store.features.simd == False
+    assert store.features.threads == True
+
+    with pytest.raises(ValueError):
+        engine.Universal(Compiler, features={'foo': True})
+
+    store = Store(engine.Universal(Compiler, features=wasmer.Features(multi_value=False)))
+
+    assert store.features.multi_value == False
+
+    with pytest.raises(ValueError):
+        engine.Universal(features={'simd': False})
+
+def test_store_deterministic():
+    from wasmer_compiler_cranelift import Compiler
+
+    assert Store().deterministic == False
+
+    store = Store(deterministic=True)
+
+    assert store.deterministic == True
+
+    module = Module(store, '(module (func (export "nan") (result f32) f32.const 0 f32.const 0 f32.div))')
+    result = Instance(module).exports.nan()
+
+    assert result != result
+
+    store = Store(engine.Universal(Compiler, deterministic=True), deterministic=True)
+
+    assert store.deterministic == True
+
+    with pytest.raises(ValueError):
+        Store(engine.Universal(Compiler), deterministic=True)
+
+    with pytest.raises(ValueError):
+        engine.Universal(deterministic=True)
+
+def test_store_interruptible():
+    assert Store().interruptible == False
+
+    store = Store(interruptible=True)
+
+    assert store.interruptible == True
+
+    # The engine instruments each module it compiles, not only the
+    # first one.
+    for index in range(3):
+        module = Module(
+            store,
+            """
+            (module
+              (global (export "g") i32 (i32.const {}))
+              (func (export "f") (result i32)
+                (block $done
+                  (loop $continue
+                    br $done))
+                global.get 0))
+            """.format(index)
+        )
+        instance = Instance(module)
+
+        assert instance.exports.f() == index
+
+        # The interruption flag isn't visible from Python.
+        assert [export.name for export in module.exports] == ['g', 'f']
+        assert [export.name for export in module.iter_exports()] == ['g', 'f']
+        assert module.export_count == 2
+        assert [name for (name, _) in instance.exports] == ['g', 'f']
+        assert len(instance.globals) == 1
+
+def test_store_without_compiler(monkeypatch):
+    import sys
+    import wasmer_compiler_cranelift
+
+    for compiler in ('wasmer_compiler_cranelift', 'wasmer_compiler_llvm', 'wasmer_compiler_singlepass'):
+        monkeypatch.setitem(sys.modules, compiler, None)
+
+    store = Store()
+
+    assert store.compiler_name == None
+
+    with pytest.raises(RuntimeError) as context_manager:
+        Module(store, '(module)')
+
+    assert str(context_manager.value).startswith('No compiler available; install wasmer-compiler-cranelift')
+
+    # An explicit headless engine is fine for deserialize-only use.
+    serialized_module = Module(Store(engine.Universal(wasmer_compiler_cranelift.Compiler)), '(module)').serialize()
+    Module.deserialize(Store(engine.Universal()), serialized_module)
@@ -1,4 +1,4 @@
-from wasmer import Instance, Module, Store, Table, TableType, Type
+from wasmer import Instance, Module, Store, Table, TableType, Type, Value
 import pytest
 
 TEST_BYTES = """
@@ -20,5 +20,127 @@ def test_type():
     assert type.maximum == None
     assert str(type) == 'TableType(type: FuncRef, minimum: 0, maximum: None)'
 
+def test_type_element_type():
+    imports = Module(
+        Store(),
+        """
+        (module
+          (import "env" "functions" (table 1 2 funcref))
+          (import "env" "objects" (table 3 externref)))
+        """
+    ).imports
+
+    functions = imports[0].type
+    objects = imports[1].type
+
+    assert str(functions) == 'TableType(type: FuncRef, minimum: 1, maximum: Some(2))'
+    assert functions.is_funcref == True
+    assert functions.is_externref == False
+
+    assert str(objects) == 'TableType(type: ExternRef, minimum: 3, maximum: None)'
+    assert objects.is_funcref == False
+    assert objects.is_externref == True
+
 def test_size():
     assert instance().exports.table.size == 0
+
+def test_len():
+    assert len(instance().exports.table) == 0
+
+def test_getitem():
+    module = Module(
+        Store(),
+        """
+        (module
+          (func $f)
+          (table (export "table") 2 funcref)
+          (elem (i32.const 0) $f))
+        """
+    )
+    table = Instance(module).exports.table
+
+    assert len(table) == 2
+    assert isinstance(table[0], Value)
+    assert table[1] == None
+
+    with pytest.raises(IndexError):
+        table[2]
+
+    with pytest.raises(IndexError):
+        table[-1]
+
+def test_set():
+    from wasmer import Function
+
+    def answer() -> int:
+        return 42
+
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (type $answer_t (func (result i32)))
+          (import "env" "answer" (func $answer (type $answer_t)))
+          (export "answer" (func $answer))
+          (func $f (result i32) i32.const 7)
+          (table (export "table") 2 funcref)
+          (table (export "externs") 1 externref)
+          (elem (table 0) (i32.const 1) func $f)
+          (func (export "call") (param i32) (result i32)
+            (call_indirect (type $answer_t) (local.get 0))))
+        """
+    )
+    exports = Instance(module, {"env": {"answer": Function(store, answer)}}).exports
+    table = exports.table
+
+    table.set(0, exports.answer)
+    assert exports.call(0) == 42
+
+    with pytest.raises(TypeError):
+        table.set(0, Function(store, answer))
+
+    table[0] = table[1]
+    assert exports.call(0) == 7
+
+    with pytest.raises(TypeError) as context_manager:
+        exports.externs.set(0, table[1])
+
+    assert str(context_manager.value) == 'Cannot set a value of type `FuncRef` in a table of `ExternRef` elements'
+
+    with pytest.raises(TypeError):
+        table.set(0, Value.i32(42))
+
+    with pytest.raises(IndexError):
+        table.set(2, table[1])
+
+def test_set_range():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (type $t (func (result i32)))
+          (table (export "table") 3 funcref)
+          (func (export "one") (result i32) i32.const 1)
+          (func (export "two") (result i32) i32.const 2)
+          (func (export "call") (param i32) (result i32)
+            (call_indirect (type $t) (local.get 0))))
+        """
+    )
+    exports = Instance(module).exports
+    table = exports.table
+
+    table.set_range(1, [exports.one, exports.two])
+    assert table[0] == None
+    assert exports.call(1) == 1
+    assert exports.call(2) == 2
+
+    with pytest.raises(IndexError):
+        table.set_range(2, [exports.one, exports.two])
+
+    with pytest.raises(TypeError):
+        table.set_range(0, [exports.two, Value.i32(42)])
+
+    # Nothing has been written when a value is invalid.
+    assert table[0] == None
@@ -5,6 +5,12 @@ import os
 import platform
 import pytest
 
+def test_target_submodule():
+    import wasmer
+
+    assert wasmer.target is target
+    assert {'Target', 'Triple', 'CpuFeatures'} <= set(dir(target))
+
 def test_triple():
     triple = target.Triple('x86_64-apple-darwin')
 
@@ -34,15 +40,46 @@ def test_cpu_features():
     cpu_features.add('avx512vl')
     cpu_features.add('lzcnt')
 
+def test_cpu_features_from_list():
+    cpu_features = target.CpuFeatures.from_list(['sse2', 'avx', 'avx2'])
+
+    assert isinstance(cpu_features, target.CpuFeatures)
+
+    with pytest.raises(ValueError) as context_manager:
+        target.CpuFeatures.from_list(['sse2', 'foo', 'bar'])
+
+    assert str(context_manager.value) == 'The CPU feature `foo` is invalid'
+
+def test_cpu_features_repr():
+    cpu_features = target.CpuFeatures.from_list(['avx', 'sse2'])
+
+    assert repr(cpu_features) == "CpuFeatures(['sse2', 'avx'])"
+    assert str(cpu_features) == "CpuFeatures(['sse2', 'avx'])"
+    assert repr(target.CpuFeatures()) == 'CpuFeatures([])'
+
 def test_target():
     triple = target.Triple.host()
     cpu_features = target.CpuFeatures()
     target_ = target.Target(triple, cpu_features)
 
+def test_target_triple_and_cpu_features():
+    triple = target.Triple('x86_64-apple-darwin')
+    cpu_features = target.CpuFeatures.from_list(['sse2', 'avx'])
+    target_ = target.Target(triple, cpu_features)
+
+    assert isinstance(target_.triple, target.Triple)
+    assert str(target_.triple) == 'x86_64-apple-darwin'
+    assert isinstance(target_.cpu_features, target.CpuFeatures)
+    assert repr(target_.cpu_features) == "CpuFeatures(['sse2', 'avx'])"
+    assert repr(target_) == "Target(triple='x86_64-apple-darwin', cpu_features=CpuFeatures(['sse2', 'avx']))"
+
 def test_target_with_default_cpu_features():
     triple = target.Triple.host()
     target_ = target.Target(triple)
 
+def test_target_host():
+    assert isinstance(target.Target.host(), target.Target)
+
 @pytest.mark.skip(reason = 'CI does not have `gcc` or `clang` installed for the moment. It will be resolved once LLVM is installed.')
 def test_cross_compilation_roundtrip():
     triple = target.Triple('x86_64-linux-musl')
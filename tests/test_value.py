@@ -29,3 +29,66 @@ def test_f64_auto_cast():
 
 def test_v128():
     assert repr(Value.v128(340282366920938463463374607431768211455)) == 'V128(340282366920938463463374607431768211455)'
+
+def test_v128_from_i32x4():
+    value = Value.v128_from_i32x4(1, 2, 3, -1)
+
+    assert repr(value) == 'V128(340282366841710301004450502067942850561)'
+    assert value.as_i32x4() == (1, 2, 3, -1)
+
+def test_v128_from_i64x2():
+    value = Value.v128_from_i64x2(1, -2)
+
+    assert value.as_i64x2() == (1, -2)
+    assert value.as_i32x4() == (1, 0, -2, -1)
+
+def test_v128_from_f32x4():
+    assert Value.v128_from_f32x4(1.5, 2.5, -3.5, 4.5).as_f32x4() == (1.5, 2.5, -3.5, 4.5)
+
+def test_v128_lanes_on_non_v128():
+    with pytest.raises(TypeError):
+        Value.i32(42).as_i32x4()
+
+def test_int():
+    assert int(Value.i32(42)) == 42
+    assert int(Value.i64(-7)) == -7
+    assert int(Value.v128(340282366920938463463374607431768211455)) == 340282366920938463463374607431768211455
+
+    with pytest.raises(TypeError):
+        int(Value.f32(4.2))
+
+def test_float():
+    assert float(Value.f32(1.5)) == 1.5
+    assert float(Value.f64(4.2)) == 4.2
+
+    with pytest.raises(TypeError):
+        float(Value.i32(42))
+
+def test_is_nan():
+    assert Value.f32(float('nan')).is_nan()
+    assert Value.f64(float('nan')).is_nan()
+    assert not Value.f64(4.2).is_nan()
+    assert not Value.f64(float('inf')).is_nan()
+    assert not Value.i32(42).is_nan()
+    assert not Value.v128(42).is_nan()
+
+def test_is_infinite():
+    assert Value.f32(float('inf')).is_infinite()
+    assert Value.f64(float('-inf')).is_infinite()
+    assert not Value.f32(4.2).is_infinite()
+    assert not Value.f64(float('nan')).is_infinite()
+    assert not Value.i64(42).is_infinite()
+
+def test_compare_with_numbers():
+    assert Value.i32(3) == 3
+    assert Value.i64(3) == 3
+    assert Value.f32(1.5) == 1.5
+    assert Value.f64(3.0) == 3
+    assert Value.i32(3) == Value.i64(3)
+    assert Value.i32(3) != 4
+    assert Value.i32(3) != '3'
+    assert Value.i32(3) != None
+    assert Value.i32(3) < 4
+
+    assert hash(Value.i32(3)) == hash(3)
+    assert len({Value.i32(3), 3}) == 1
@@ -18,6 +18,14 @@ def test_wasi_version():
 def test_wasi_get_version():
     assert wasi.get_version(Module(Store(), TEST_BYTES), strict=True) == wasi.Version.SNAPSHOT1
 
+def test_wasi_detect_version():
+    assert wasi.detect_version(TEST_BYTES, strict=True) == wasi.Version.SNAPSHOT1
+    assert wasi.detect_version(TEST_BYTES, strict=False) == wasi.Version.SNAPSHOT1
+    assert wasi.detect_version(b'\x00asm\x01\x00\x00\x00', strict=False) == None
+
+    with pytest.raises(ValueError):
+        wasi.detect_version(b'not wasm', strict=True)
+
 def test_wasi_state_builder():
     state_builder = \
         wasi.StateBuilder("test-program"). \
@@ -27,6 +35,43 @@ def test_wasi_state_builder():
 
     assert isinstance(state_builder, wasi.StateBuilder)
 
+def test_wasi_state_builder_path_like(tmp_path):
+    from pathlib import Path
+
+    subdir = tmp_path / 'subdir_é'
+    subdir.mkdir()
+
+    state_builder = \
+        wasi.StateBuilder("test-program", preopen_directories=[Path('.')]). \
+            preopen_directories([subdir]). \
+            preopen_directory(Path(here)). \
+            map_directories({"foo": subdir}). \
+            map_directory("bar", Path('.'))
+
+    assert isinstance(state_builder, wasi.StateBuilder)
+
+@pytest.mark.skipif(not sys.platform.startswith('linux'), reason='file descriptors preopening is Linux only')
+def test_wasi_state_builder_preopen_fd():
+    fd = os.open(here, os.O_RDONLY)
+
+    try:
+        state_builder = wasi.StateBuilder("test-program").preopen_fd(fd, "tests")
+
+        assert isinstance(state_builder, wasi.StateBuilder)
+        assert isinstance(state_builder.finalize(), wasi.Environment)
+    finally:
+        os.close(fd)
+
+@pytest.mark.skipif(not sys.platform.startswith('linux'), reason='file descriptors preopening is Linux only')
+def test_wasi_state_builder_preopen_invalid_fd():
+    with pytest.raises(OSError):
+        wasi.StateBuilder("test-program").preopen_fd(-1, "foo")
+
+@pytest.mark.skipif(sys.platform.startswith('linux'), reason='file descriptors preopening is Linux only')
+def test_wasi_state_builder_preopen_fd_unsupported():
+    with pytest.raises(NotImplementedError):
+        wasi.StateBuilder("test-program").preopen_fd(0, "foo")
+
 def test_wasi_env():
     assert isinstance(wasi.StateBuilder("foo").finalize(), wasi.Environment)
 
@@ -89,6 +134,74 @@ def test_wasi_imports():
         'sock_shutdown'
     ]
 
+def test_wasi_state_builder_reuse():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "wasi_snapshot_preview1" "args_sizes_get" (func $args_sizes_get (param i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (func (export "args_sizes") (result i32 i32)
+            (drop (call $args_sizes_get (i32.const 0) (i32.const 4)))
+            (i32.load (i32.const 0))
+            (i32.load (i32.const 4))))
+        """
+    )
+
+    def args_sizes(state_builder):
+        wasi_env = state_builder.finalize()
+        import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
+
+        return Instance(module, import_object).exports.args_sizes()
+
+    state_builder = wasi.StateBuilder("test-program").argument("--foo")
+
+    assert state_builder.program_name == "test-program"
+    assert args_sizes(state_builder) == (2, len(b"test-program\0--foo\0"))
+
+    state_builder.clear_arguments().arguments(["-a", "-b"])
+    state_builder.program_name = "x"
+
+    assert state_builder.program_name == "x"
+    assert args_sizes(state_builder) == (3, len(b"x\0-a\0-b\0"))
+
+def test_wasi_state_builder_clear_environments():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "wasi_snapshot_preview1" "environ_sizes_get" (func $environ_sizes_get (param i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (func (export "environ_sizes") (result i32 i32)
+            (drop (call $environ_sizes_get (i32.const 0) (i32.const 4)))
+            (i32.load (i32.const 0))
+            (i32.load (i32.const 4))))
+        """
+    )
+
+    def environ_sizes(state_builder):
+        wasi_env = state_builder.finalize()
+        import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
+
+        return Instance(module, import_object).exports.environ_sizes()
+
+    state_builder = wasi.StateBuilder("test-program").environment("A", "B")
+
+    assert environ_sizes(state_builder) == (1, len(b"A=B\0"))
+
+    state_builder.environments({"C": "D"})
+
+    assert environ_sizes(state_builder) == (2, len(b"A=B\0C=D\0"))
+
+    state_builder.clear_environments().environment("XY", "Z")
+
+    assert environ_sizes(state_builder) == (1, len(b"XY=Z\0"))
+
+    with pytest.raises(RuntimeError):
+        wasi.StateBuilder("test-program").environment("A=", "B").finalize()
+
 def test_wasi_env_memory():
     store = Store()
     wasi_env = wasi.StateBuilder("foo").finalize()
@@ -96,6 +209,25 @@ def test_wasi_env_memory():
 
     instance = Instance(Module(store, TEST_BYTES), import_object)
 
+def test_wasi_env_set_memory():
+    from wasmer import Memory, MemoryType
+
+    store = Store()
+    wasi_env = wasi.StateBuilder("foo").finalize()
+    wasi_env.memory = Memory(store, MemoryType(minimum=1))
+    wasi_env.generate_import_object(store, wasi.Version.LATEST)
+
+    with pytest.raises(TypeError):
+        wasi_env.memory = 42
+
+    other_store = Store()
+
+    with pytest.raises(RuntimeError):
+        wasi_env.memory = Memory(other_store, MemoryType(minimum=1))
+
+    with pytest.raises(RuntimeError):
+        wasi_env.generate_import_object(other_store, wasi.Version.LATEST)
+
 def test_wasi():
     store = Store()
     wasi_env = \
@@ -108,3 +240,184 @@ def test_wasi():
 
     instance = Instance(Module(store, TEST_BYTES), import_object)
     instance.exports._start()
+
+def test_wasi_exit_code():
+    store = Store()
+    module = Module(
+        store,
+        """
+        (module
+          (import "wasi_snapshot_preview1" "proc_exit" (func $proc_exit (param i32)))
+          (memory (export "memory") 1)
+          (func (export "_start")
+            i32.const 7
+            call $proc_exit))
+        """
+    )
+    wasi_env = wasi.StateBuilder("test-program").finalize()
+    import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
+    instance = Instance(module, import_object)
+
+    assert wasi_env.get_exit_code() == None
+
+    with pytest.raises(RuntimeError):
+        instance.exports._start()
+
+    assert wasi_env.get_exit_code() == 7
+
+def test_wasi_state_builder_build_import_object():
+    store = Store()
+    module = Module(store, TEST_BYTES)
+    import_object, wasi_env = wasi.StateBuilder("test-program").build_import_object(store, module)
+
+    assert isinstance(import_object, ImportObject)
+    assert isinstance(wasi_env, wasi.Environment)
+
+    instance = Instance(module, import_object)
+
+    with pytest.raises(RuntimeError):
+        wasi.StateBuilder("test-program").build_import_object(store, Module(store, "(module)"))
+
+def test_wasi_state_builder_sandboxed(tmp_path):
+    store = Store()
+    module = Module(store, TEST_BYTES)
+    import_object, wasi_env = \
+        wasi.StateBuilder.sandboxed("test-program", str(tmp_path)). \
+            argument("--foo"). \
+            build_import_object(store, module)
+
+    instance = Instance(module, import_object)
+    instance.exports._start()
+
+    stdout = wasi_env.read_stdout().decode()
+
+    assert "Found program name: `test-program`" in stdout
+    assert "Found 1 arguments: --foo" in stdout
+    assert "Found 0 environment variables" in stdout
+    assert wasi_env.read_stdout() == b''
+    assert wasi_env.read_stderr() == b''
+
+FD_READ_WAT = """
+(module
+  (import "wasi_snapshot_preview1" "path_open"
+    (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_read"
+    (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "data/input.txt")
+  (func (export "read") (result i32)
+    (drop
+      (call $path_open
+        (i32.const 4) ;; the preopened root, after the virtual one
+        (i32.const 0) (i32.const 0) (i32.const 14) (i32.const 0)
+        (i64.const 2) ;; the right to read
+        (i64.const 0) (i32.const 0) (i32.const 16)))
+    ;; Read up to 64 bytes at offset 64.
+    (i32.store (i32.const 32) (i32.const 64))
+    (i32.store (i32.const 36) (i32.const 64))
+    (call $fd_read (i32.load (i32.const 16)) (i32.const 32) (i32.const 1) (i32.const 24))))
+"""
+
+def test_wasi_state_builder_virtual_fs():
+    store = Store()
+    module = Module(store, FD_READ_WAT)
+    import_object, _ = \
+        wasi.StateBuilder("test-program"). \
+            virtual_fs({"/data/input.txt": b"Hello, World!", "readme": b""}). \
+            build_import_object(store, module)
+
+    instance = Instance(module, import_object)
+
+    assert instance.exports.read() == 0
+    assert bytearray(instance.exports.memory.buffer)[64:77] == b"Hello, World!"
+
+    with pytest.raises(TypeError):
+        wasi.StateBuilder("test-program").virtual_fs({"/data/input.txt": 42})
+
+PATH_OPEN_WAT = """
+(module
+  (import "wasi_snapshot_preview1" "path_open"
+    (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))
+  (memory (export "memory") 2)
+  (data (i32.const 0) ".")
+  (func (export "open") (result i32)
+    (call $path_open
+      (i32.const 4) ;; the preopened directory, after the root one
+      (i32.const 0) (i32.const 0) (i32.const 1) (i32.const 0)
+      (i64.const 0) (i64.const 0) (i32.const 0) (i32.const 16))))
+"""
+
+def test_wasi_state_builder_limits_open_fds():
+    store = Store()
+    module = Module(store, PATH_OPEN_WAT)
+    import_object, _ = \
+        wasi.StateBuilder("test-program"). \
+            preopen_directory("."). \
+            limits(max_open_fds=6). \
+            build_import_object(store, module)
+
+    instance = Instance(module, import_object)
+    EMFILE = 33
+
+    # Standard input, output and error, the root directory and the
+    # preopened directory are open: one file descriptor is left.
+    assert instance.exports.open() == 0
+    assert instance.exports.open() == EMFILE
+
+def test_wasi_state_builder_limits_memory():
+    store = Store()
+    module = Module(store, PATH_OPEN_WAT)
+    import_object, _ = \
+        wasi.StateBuilder("test-program"). \
+            limits(max_memory_bytes=3 * 65536 + 42). \
+            build_import_object(store, module)
+
+    instance = Instance(module, import_object)
+    memory = instance.exports.memory
+
+    assert memory.grow(1) == 2
+
+    with pytest.raises(RuntimeError):
+        memory.grow(1)
+
+    import_object, _ = \
+        wasi.StateBuilder("test-program"). \
+            limits(max_memory_bytes=65536). \
+            build_import_object(store, module)
+
+    with pytest.raises(RuntimeError):
+        Instance(module, import_object)
+
+    with pytest.raises(ValueError):
+        wasi.StateBuilder("test-program").limits(max_memory_bytes=2 ** 40)
+
+def test_wasi_state_builder_limits_memory_generate_imports():
+    store = Store()
+    wasi_env = \
+        wasi.StateBuilder("test-program"). \
+            limits(max_memory_bytes=3 * 65536). \
+            finalize()
+
+    with pytest.raises(ValueError) as context_manager:
+        wasi_env.generate_imports(store, wasi.Version.SNAPSHOT1)
+
+    assert 'generate_import_object' in str(context_manager.value)
+
+    import_object = wasi_env.generate_import_object(store, wasi.Version.SNAPSHOT1)
+
+    assert isinstance(import_object, ImportObject)
+
+@pytest.mark.skipif(not sys.platform.startswith('linux'), reason='file descriptors preopening is Linux only')
+def test_wasi_state_builder_preopen_fd_outlives_fd():
+    store = Store()
+    module = Module(store, PATH_OPEN_WAT)
+    fd = os.open(here, os.O_RDONLY)
+    state_builder = wasi.StateBuilder("test-program").preopen_fd(fd, "tests")
+
+    # The file descriptor has been duplicated.
+    os.close(fd)
+
+    import_object, _ = state_builder.build_import_object(store, module)
+    instance = Instance(module, import_object)
+
+    assert instance.exports.open() == 0
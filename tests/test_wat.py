@@ -1,11 +1,57 @@
 from wasmer import wat2wasm, wasm2wat, Instance, Module, Store
+import pytest
 
 def test_wat2wasm():
     assert wat2wasm('(module)') == b'\x00asm\x01\x00\x00\x00'
 
+def test_wat2wasm_from_buffer():
+    assert wat2wasm(b'(module)') == b'\x00asm\x01\x00\x00\x00'
+    assert wat2wasm(bytearray(b'(module)')) == b'\x00asm\x01\x00\x00\x00'
+    assert wat2wasm(memoryview(b'(module)')) == b'\x00asm\x01\x00\x00\x00'
+
+def test_wat2wasm_invalid_utf8():
+    with pytest.raises(ValueError) as context_manager:
+        wat2wasm(b'(module \xff)')
+
+    assert str(context_manager.value).startswith('The WAT source is not valid UTF-8')
+
+def test_wat2wasm_error():
+    from wasmer import WatError
+
+    with pytest.raises(WatError) as context_manager:
+        wat2wasm('(module\n  (func (result i32)\n    i32.const x))')
+
+    error = context_manager.value
+
+    assert isinstance(error, ValueError)
+    assert isinstance(error, RuntimeError)
+    assert error.line == 3
+    assert error.column == 15
+    assert error.text == '    i32.const x))'
+
 def test_wasm2wat():
     assert wasm2wat(b'\x00asm\x01\x00\x00\x00') == '(module)'
 
+def test_wasm2wat_canonical():
+    wasm_bytes = wat2wasm(
+        """
+        (module
+          (func (export "f;; (") (param i32) (result i32)
+            local.get 0))
+        """
+    )
+    canonical = wasm2wat(wasm_bytes, canonical=True)
+
+    assert canonical == (
+        '(module\n'
+        '  (type (func (param i32) (result i32)))\n'
+        '  (func (type 0) (param i32) (result i32)\n'
+        '    local.get 0)\n'
+        '  (export "f;; (" (func 0)))'
+    )
+    assert wat2wasm(canonical) == wasm_bytes
+    assert wasm2wat(wasm_bytes, canonical=False) == wasm2wat(wasm_bytes)
+
 def test_wat2wasm2instance():
     wat = """ (module
                 (type (func (param i32 i32) (result i32)))
@@ -18,3 +64,14 @@ def test_wat2wasm2instance():
     instance = Instance(Module(Store(), wasm_bytes))
 
     assert instance.exports.sum(1, 2) == 3
+
+def test_wat2wasm_name_section():
+    source = '(module (func $answer (export "answer") (result i32) i32.const 42))'
+
+    with_names = wat2wasm(source)
+    without_names = wat2wasm(source, generate_name_section=False)
+
+    assert '$answer' in wasm2wat(with_names)
+    assert '$answer' not in wasm2wat(without_names)
+    assert len(without_names) < len(with_names)
+    assert Instance(Module(Store(), without_names)).exports.answer() == 42